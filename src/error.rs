@@ -27,3 +27,51 @@ impl<T> fmt::Debug for CapacityError<T> {
         write!(f, "CapacityError: {}", CAPERROR)
     }
 }
+
+/// Error value returned by `ArrayDeque::checked_insert`
+///
+/// Both variants hand back the element that could not be inserted.
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+pub enum InsertError<T> {
+    /// The index was greater than the length of the deque.
+    OutOfBounds(T),
+    /// The deque was full.
+    Full(T),
+}
+
+impl<T> InsertError<T> {
+    /// Extract the element that could not be inserted.
+    pub fn into_element(self) -> T {
+        match self {
+            InsertError::OutOfBounds(element) | InsertError::Full(element) => element,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            InsertError::OutOfBounds(_) => "index out of bounds",
+            InsertError::Full(_) => CAPERROR,
+        }
+    }
+}
+
+impl<T> From<CapacityError<T>> for InsertError<T> {
+    fn from(error: CapacityError<T>) -> Self {
+        InsertError::Full(error.element)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Error for InsertError<T> {}
+
+impl<T> fmt::Display for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl<T> fmt::Debug for InsertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "InsertError: {}", self.description())
+    }
+}
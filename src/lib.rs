@@ -14,8 +14,24 @@
 //!
 //! - `std`
 //!   - Optional, enabled by default
-//!   - Conversions between `ArrayDeque` and `Vec`
+//!   - `std::io` helpers for byte deques
 //!   - Use libstd
+//!   - Implies `alloc`
+//!
+//! - `alloc`
+//!   - Optional, enabled by `std`
+//!   - Conversions between `ArrayDeque` and `Vec`
+//!   - Use liballoc
+//!
+//! - `rayon`
+//!   - Optional
+//!   - Parallel iteration over the elements with `par_iter` and `par_iter_mut`
+//!   - Current releases of rayon require Rust 1.80 or later, above the
+//!     crate's own minimum of 1.59
+//!
+//! - `serde`
+//!   - Optional
+//!   - Compact serialization of byte deques with the `serde_bytes` module
 //!
 //! # Usage
 //!
@@ -55,25 +71,38 @@
 #[cfg(not(any(feature = "std", test)))]
 extern crate core as std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use std::cmp;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use std::iter::{FromIterator, Product, Sum};
 use std::marker;
+use std::mem;
 use std::mem::MaybeUninit;
 use std::ops::Index;
 use std::ops::IndexMut;
+use std::ops::Range;
 use std::ptr;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use behavior::Behavior;
 
 pub mod behavior;
 mod error;
 mod range;
 
+#[cfg(feature = "rayon")]
+mod par_iter;
+#[cfg(feature = "serde")]
+pub mod serde_bytes;
+
 pub use behavior::{Saturating, Wrapping};
-pub use error::CapacityError;
+pub use error::{CapacityError, InsertError};
 pub use range::RangeArgument;
 
 /// A fixed capacity ring buffer.
@@ -85,6 +114,8 @@ pub use range::RangeArgument;
 /// to back.
 pub struct ArrayDeque<T, const CAP: usize, B: Behavior = Saturating> {
     xs: MaybeUninit<[T; CAP]>,
+    // Picking a narrower integer type from `CAP` for `tail` and `len` would
+    // need `generic_const_exprs`, which is unstable, so both stay `usize`.
     tail: usize,
     len: usize,
     marker: marker::PhantomData<B>,
@@ -165,6 +196,126 @@ impl<T, const CAP: usize> ArrayDeque<T, CAP, Saturating> {
         }
     }
 
+    /// Add an element to the front of the deque.
+    ///
+    /// Equivalent to [`push_front`](#method.push_front), named after
+    /// `ArrayVec::try_push`: the element is handed back in the error if the
+    /// deque is full, and nothing is evicted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.try_push_front(1), Ok(()));
+    /// assert_eq!(buf.try_push_front(2), Err(CapacityError { element: 2 }));
+    /// ```
+    #[inline]
+    pub fn try_push_front(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.push_front(element)
+    }
+
+    /// Add an element to the back of the deque.
+    ///
+    /// Equivalent to [`push_back`](#method.push_back), named after
+    /// `ArrayVec::try_push`: the element is handed back in the error if the
+    /// deque is full, and nothing is evicted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.try_push_back(1), Ok(()));
+    /// assert_eq!(buf.try_push_back(2), Err(CapacityError { element: 2 }));
+    /// ```
+    #[inline]
+    pub fn try_push_back(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        self.push_back(element)
+    }
+
+    /// Add an element to the front of the deque, kicking out the backmost
+    /// element if the deque is full.
+    ///
+    /// This behaves like `push_front` on `ArrayDeque<_, _, Wrapping>`: return
+    /// `None` if deque still has capacity, or `Some(existing)` if the deque is
+    /// full, where `existing` is the backmost element being kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // 3 -(+)-> [2, 1] => [3, 2] -> Some(1)
+    ///
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.force_push_front(1), None);
+    /// assert_eq!(buf.force_push_front(2), None);
+    /// assert_eq!(buf.force_push_front(3), Some(1));
+    /// assert_eq!(buf, [3, 2].into());
+    /// ```
+    pub fn force_push_front(&mut self, element: T) -> Option<T> {
+        let existing = if self.is_full() {
+            if self.capacity() == 0 {
+                return Some(element);
+            } else {
+                self.pop_back()
+            }
+        } else {
+            None
+        };
+
+        unsafe {
+            self.push_front_unchecked(element);
+        }
+
+        existing
+    }
+
+    /// Add an element to the back of the deque, kicking out the frontmost
+    /// element if the deque is full.
+    ///
+    /// This behaves like `push_back` on `ArrayDeque<_, _, Wrapping>`: return
+    /// `None` if deque still has capacity, or `Some(existing)` if the deque is
+    /// full, where `existing` is the frontmost element being kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // [1, 2] <-(+)- 3 => [2, 3] -> Some(1)
+    ///
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.force_push_back(1), None);
+    /// assert_eq!(buf.force_push_back(2), None);
+    /// assert_eq!(buf.force_push_back(3), Some(1));
+    /// assert_eq!(buf, [2, 3].into());
+    /// ```
+    pub fn force_push_back(&mut self, element: T) -> Option<T> {
+        let existing = if self.is_full() {
+            if self.capacity() == 0 {
+                return Some(element);
+            } else {
+                self.pop_front()
+            }
+        } else {
+            None
+        };
+
+        unsafe {
+            self.push_back_unchecked(element);
+        }
+
+        existing
+    }
+
     /// Inserts an element at `index` within the `ArrayDeque`. Whichever
     /// end is closer to the insertion point will be moved to make room,
     /// and all the affected elements will be moved to new positions.
@@ -215,6 +366,138 @@ impl<T, const CAP: usize> ArrayDeque<T, CAP, Saturating> {
         Ok(())
     }
 
+    /// Inserts an element at `index` within the `ArrayDeque`.
+    ///
+    /// This is the same as [`insert`](#method.insert), named after
+    /// `ArrayVec::insert_within_capacity` for familiarity: it returns
+    /// `Err(CapacityError { *element* })` if the deque is full, but still
+    /// panics if `index` is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than `ArrayDeque`'s length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.insert_within_capacity(0, 2), Ok(()));
+    /// assert_eq!(buf.insert_within_capacity(0, 1), Ok(()));
+    /// assert_eq!(buf.insert_within_capacity(1, 3), Err(CapacityError { element: 3 }));
+    /// assert_eq!(buf, [1, 2].into());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert_within_capacity(
+        &mut self,
+        index: usize,
+        element: T,
+    ) -> Result<(), CapacityError<T>> {
+        self.insert(index, element)
+    }
+
+    /// Inserts an element at `index` within the `ArrayDeque`, without
+    /// panicking on a bad index.
+    ///
+    /// Return `Ok(())` if the insertion succeeds. Otherwise the element is
+    /// handed back in `Err(InsertError::OutOfBounds(element))` if `index` is
+    /// greater than the length, or in `Err(InsertError::Full(element))` if
+    /// the deque is full. The bounds are checked first.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, InsertError};
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.checked_insert(1, 1), Err(InsertError::OutOfBounds(1)));
+    /// assert_eq!(buf.checked_insert(0, 2), Ok(()));
+    /// assert_eq!(buf.checked_insert(0, 1), Ok(()));
+    /// assert_eq!(buf.checked_insert(1, 3), Err(InsertError::Full(3)));
+    /// assert_eq!(buf, [1, 2].into());
+    /// ```
+    #[inline]
+    pub fn checked_insert(&mut self, index: usize, element: T) -> Result<(), InsertError<T>> {
+        if index > self.len() {
+            return Err(InsertError::OutOfBounds(element));
+        }
+        self.insert(index, element).map_err(InsertError::from)
+    }
+
+    /// Inserts all elements of `src` at `index` within the `ArrayDeque`,
+    /// preserving their order.
+    ///
+    /// Whichever end is closer to the insertion point is moved once to make
+    /// room for the whole slice.
+    ///
+    /// Return `Ok(())` if the insertion succeeds, or return `Err(CapacityError)`
+    /// leaving the deque unchanged if `src` doesn't fit in the remaining
+    /// capacity.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than `ArrayDeque`'s length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [1, 5].into();
+    ///
+    /// assert_eq!(buf.insert_slice(1, &[2, 3, 4]), Ok(()));
+    /// assert_eq!(buf, [1, 2, 3, 4, 5].into());
+    ///
+    /// assert_eq!(buf.insert_slice(0, &[0]), Err(CapacityError { element: () }));
+    /// ```
+    #[track_caller]
+    pub fn insert_slice(&mut self, index: usize, src: &[T]) -> Result<(), CapacityError>
+    where
+        T: Copy,
+    {
+        let len = self.len();
+        assert!(index <= len, "index out of bounds");
+
+        let count = src.len();
+        if count > CAP - len {
+            return Err(CapacityError { element: () });
+        }
+        if count == 0 {
+            return Ok(());
+        }
+
+        unsafe {
+            let tail = self.tail();
+            if index < len - index {
+                // move the elements before `index` towards the front
+                let new_tail = Self::wrap_sub(tail, count);
+                self.wrap_copy(new_tail, tail, index);
+                self.set_tail(new_tail);
+            } else {
+                // move the elements after `index` towards the back
+                let src_idx = Self::wrap_add(tail, index);
+                let dst_idx = Self::wrap_add(tail, index + count);
+                self.wrap_copy(dst_idx, src_idx, len - index);
+            }
+            self.set_len(len + count);
+
+            let idx = Self::wrap_add(self.tail(), index);
+            let first = cmp::min(count, CAP - idx);
+            ptr::copy_nonoverlapping(src.as_ptr(), self.ptr_mut().add(idx), first);
+            ptr::copy_nonoverlapping(src.as_ptr().add(first), self.ptr_mut(), count - first);
+        }
+
+        Ok(())
+    }
+
     /// Extend deque from front with the contents of an iterator.
     ///
     /// Does not extract more items than there is space for.
@@ -253,6 +536,69 @@ impl<T, const CAP: usize> ArrayDeque<T, CAP, Saturating> {
         }
     }
 
+    /// Extend deque from front with the contents of a slice.
+    ///
+    /// This is equivalent to `extend_front(src.iter().copied())`, but moves
+    /// the tail once and copies the elements in bulk.
+    ///
+    /// Does not copy more items than there is space for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // [9, 8, 7] -(+)-> [_, _, _, _, _, _, _] => [7, 8, 9, _, _, _, _]
+    /// // [6, 5, 4] -(+)-> [7, 8, 9, _, _, _, _] => [4, 5, 6, 7, 8, 9, _]
+    /// // [3, 2, 1] -(+)-> [4, 5, 6, 7, 8, 9, _] => [3, 4, 5, 6, 7, 8, 9]
+    ///
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 7> = ArrayDeque::new();
+    ///
+    /// buf.extend_front_from_slice(&[9, 8, 7]);
+    /// buf.extend_front_from_slice(&[6, 5, 4]);
+    ///
+    /// assert_eq!(buf.len(), 6);
+    ///
+    /// // max capacity reached
+    /// buf.extend_front_from_slice(&[3, 2, 1]);
+    ///
+    /// assert_eq!(buf.len(), 7);
+    /// assert_eq!(buf, [3, 4, 5, 6, 7, 8, 9].into());
+    /// ```
+    pub fn extend_front_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        let take = cmp::min(src.len(), self.capacity() - self.len());
+        if take == 0 {
+            return;
+        }
+
+        let new_tail = Self::wrap_sub(self.tail(), take);
+        let first = cmp::min(take, CAP - new_tail);
+
+        // Elements pushed to the front one by one end up in reverse order, so
+        // the end of `src` fills the slots from the new tail up to the end of
+        // the buffer and the start of `src` fills the wrapped slots.
+        let (wrapped, front) = src[..take].split_at(take - first);
+        let xs = self.as_uninit_slice_mut();
+        for (slot, &element) in xs[new_tail..new_tail + first]
+            .iter_mut()
+            .zip(front.iter().rev())
+        {
+            slot.write(element);
+        }
+        for (slot, &element) in xs[..take - first].iter_mut().zip(wrapped.iter().rev()) {
+            slot.write(element);
+        }
+
+        unsafe {
+            let len = self.len();
+            self.set_tail(new_tail);
+            self.set_len(len + take);
+        }
+    }
+
     /// Extend deque from back with the contents of an iterator.
     ///
     /// Does not extract more items than there is space for.
@@ -285,62 +631,202 @@ impl<T, const CAP: usize> ArrayDeque<T, CAP, Saturating> {
     where
         I: IntoIterator<Item = T>,
     {
-        let take = self.capacity() - self.len();
-        for element in iter.into_iter().take(take) {
-            self.push_back(element);
-        }
+        self.fill_back(iter.into_iter());
     }
-}
 
-#[allow(unused_must_use)]
-impl<T, const CAP: usize> Extend<T> for ArrayDeque<T, CAP, Saturating> {
-    fn extend<I>(&mut self, iter: I)
+    /// Extend deque from back with the contents of an iterator, and return
+    /// the number of items that did not fit.
+    ///
+    /// Unlike [`extend_back`](#method.extend_back), the iterator is always
+    /// run to completion, and the items that did not fit are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.saturating_extend([1, 2, 3]), 0);
+    /// assert_eq!(buf.saturating_extend([4, 5, 6]), 2);
+    /// assert_eq!(buf, [1, 2, 3, 4].into());
+    /// ```
+    pub fn saturating_extend<I>(&mut self, iter: I) -> usize
     where
         I: IntoIterator<Item = T>,
     {
-        self.extend_back(iter);
+        let mut iter = iter.into_iter();
+        self.fill_back(&mut iter);
+        iter.count()
     }
-}
 
-impl<T, const CAP: usize> FromIterator<T> for ArrayDeque<T, CAP, Saturating> {
-    fn from_iter<I>(iter: I) -> Self
+    /// Extend deque from back with all the contents of an iterator, or not
+    /// at all.
+    ///
+    /// Return `Ok(())` if every item fits, or return `Err(CapacityError)` if
+    /// the iterator has more items than there is space for. On error, the
+    /// items already pushed are popped and dropped again, along with the
+    /// first item that did not fit, leaving the deque unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, CapacityError};
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [1].into();
+    ///
+    /// assert_eq!(buf.try_extend_exact([2, 3, 4, 5]), Err(CapacityError { element: () }));
+    /// assert_eq!(buf, [1].into());
+    ///
+    /// assert_eq!(buf.try_extend_exact([2, 3, 4]), Ok(()));
+    /// assert_eq!(buf, [1, 2, 3, 4].into());
+    /// ```
+    pub fn try_extend_exact<I>(&mut self, iter: I) -> Result<(), CapacityError>
     where
         I: IntoIterator<Item = T>,
     {
-        let mut array: ArrayDeque<_, CAP, Saturating> = ArrayDeque::new();
-        array.extend_back(iter);
-        array
-    }
-}
-
-impl<T, const CAP: usize> Clone for ArrayDeque<T, CAP, Saturating>
-where
-    T: Clone,
-{
-    fn clone(&self) -> Self {
-        self.iter().cloned().collect()
+        let len = self.len();
+        let mut iter = iter.into_iter();
+        self.fill_back(&mut iter);
+        if iter.next().is_some() {
+            let pushed = self.len() - len;
+            self.drain_back(pushed);
+            return Err(CapacityError { element: () });
+        }
+        Ok(())
     }
-}
 
-impl<T, const CAP: usize> ArrayDeque<T, CAP, Wrapping> {
-    /// Add an element to the front of the deque.
+    /// Move all the elements of `self` to the back of `dst`, consuming `self`.
     ///
-    /// Return `None` if deque still has capacity, or `Some(existing)`
-    /// if the deque is full, where `existing` is the backmost element being kicked out.
+    /// As with [`extend_back`](#method.extend_back), only the frontmost
+    /// elements that fit are moved, and the others are dropped. The elements
+    /// are moved in bulk rather than one by one.
     ///
     /// # Examples
     ///
     /// ```
-    /// // 1 -(+)-> [_, _, _] => [1, _, _] -> None
-    /// // 2 -(+)-> [1, _, _] => [2, 1, _] -> None
-    /// // 3 -(+)-> [2, 1, _] => [3, 2, 1] -> None
-    /// // 4 -(+)-> [3, 2, 1] => [4, 3, 2] -> Some(1)
+    /// use arraydeque::ArrayDeque;
     ///
-    /// use arraydeque::{ArrayDeque, Wrapping};
+    /// let mut buf: ArrayDeque<_, 4> = [1, 2].into();
+    /// let src: ArrayDeque<_, 4> = [3, 4, 5].into();
     ///
-    /// let mut buf: ArrayDeque<_, 3, Wrapping> = ArrayDeque::new();
+    /// src.concat_into(&mut buf);
     ///
-    /// buf.push_front(1);
+    /// assert_eq!(buf, [1, 2, 3, 4].into());
+    /// ```
+    pub fn concat_into(mut self, dst: &mut Self) {
+        let free = dst.capacity() - dst.len();
+        if self.len() > free {
+            let overflow = self.len() - free;
+            self.drain_back(overflow);
+        }
+        dst.move_back_from(&mut self);
+    }
+
+    /// Clear the deque and refill it with the contents of an iterator,
+    /// starting at the beginning of the underlying buffer.
+    ///
+    /// Does not extract more items than there is space for. The new elements
+    /// are always contiguous, as returned by `as_slices().0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// buf.extend_back([1, 2]);
+    /// buf.push_front(0).unwrap();
+    ///
+    /// buf.reset_to([4, 5, 6, 7]);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[4, 5, 6][..], &[][..]));
+    /// ```
+    pub fn reset_to<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.clear();
+        unsafe {
+            self.set_tail(0);
+        }
+        self.extend_back(iter);
+    }
+}
+
+#[allow(unused_must_use)]
+impl<T, const CAP: usize> Extend<T> for ArrayDeque<T, CAP, Saturating> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend_back(iter);
+    }
+}
+
+impl<'a, T, const CAP: usize> Extend<&'a T> for ArrayDeque<T, CAP, Saturating>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend_back(iter.into_iter().copied());
+    }
+}
+
+impl<T, const CAP: usize> FromIterator<T> for ArrayDeque<T, CAP, Saturating> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut array: ArrayDeque<_, CAP, Saturating> = ArrayDeque::new();
+        array.extend_back(iter);
+        array
+    }
+}
+
+impl<'a, T, const CAP: usize> FromIterator<&'a T> for ArrayDeque<T, CAP, Saturating>
+where
+    T: Copy + 'a,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl<T, const CAP: usize> Clone for ArrayDeque<T, CAP, Saturating>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const CAP: usize> ArrayDeque<T, CAP, Wrapping> {
+    /// Add an element to the front of the deque.
+    ///
+    /// Return `None` if deque still has capacity, or `Some(existing)`
+    /// if the deque is full, where `existing` is the backmost element being kicked out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // 1 -(+)-> [_, _, _] => [1, _, _] -> None
+    /// // 2 -(+)-> [1, _, _] => [2, 1, _] -> None
+    /// // 3 -(+)-> [2, 1, _] => [3, 2, 1] -> None
+    /// // 4 -(+)-> [3, 2, 1] => [4, 3, 2] -> Some(1)
+    ///
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<_, 3, Wrapping> = ArrayDeque::new();
+    ///
+    /// buf.push_front(1);
     /// buf.push_front(2);
     /// buf.push_front(3);
     ///
@@ -480,18 +966,85 @@ impl<T, const CAP: usize> ArrayDeque<T, CAP, Wrapping> {
             self.push_back(element);
         }
     }
+
+    /// Move all the elements of `self` to the back of `dst`, consuming `self`.
+    ///
+    /// As with [`extend_back`](#method.extend_back), the frontmost elements
+    /// of `dst` are dropped to make room if necessary. The elements are moved
+    /// in bulk rather than one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<_, 4, Wrapping> = [1, 2].into();
+    /// let src: ArrayDeque<_, 4, Wrapping> = [3, 4, 5].into();
+    ///
+    /// src.concat_into(&mut buf);
+    ///
+    /// assert_eq!(buf, [2, 3, 4, 5].into());
+    /// ```
+    pub fn concat_into(mut self, dst: &mut Self) {
+        let free = dst.capacity() - dst.len();
+        if self.len() > free {
+            let overflow = self.len() - free;
+            dst.drain_front(overflow);
+        }
+        dst.move_back_from(&mut self);
+    }
+
+    /// Clear the deque and refill it with the contents of an iterator,
+    /// starting at the beginning of the underlying buffer.
+    ///
+    /// Only the last `capacity()` items are kept, as with
+    /// [`extend_back`](#method.extend_back). The new elements are always
+    /// contiguous, as returned by `as_slices().0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<_, 3, Wrapping> = ArrayDeque::new();
+    /// buf.extend_back([1, 2]);
+    /// buf.push_front(0);
+    ///
+    /// buf.reset_to([4, 5, 6, 7]);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[5, 6, 7][..], &[][..]));
+    /// ```
+    pub fn reset_to<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.clear();
+        unsafe {
+            self.set_tail(0);
+        }
+        self.extend_back(iter);
+        self.compact();
+    }
 }
 
-#[allow(unused_must_use)]
 impl<T, const CAP: usize> Extend<T> for ArrayDeque<T, CAP, Wrapping> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = T>,
     {
-        let take = self.capacity() - self.len();
-        for elt in iter.into_iter().take(take) {
-            self.push_back(elt);
-        }
+        self.extend_back(iter);
+    }
+}
+
+impl<'a, T, const CAP: usize> Extend<&'a T> for ArrayDeque<T, CAP, Wrapping>
+where
+    T: Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend_back(iter.into_iter().copied());
     }
 }
 
@@ -500,12 +1053,32 @@ impl<T, const CAP: usize> FromIterator<T> for ArrayDeque<T, CAP, Wrapping> {
     where
         I: IntoIterator<Item = T>,
     {
+        let mut iter = iter.into_iter();
+        // Skip the elements that would be pushed out anyway
+        let (lower, upper) = iter.size_hint();
+        if upper == Some(lower) && lower > CAP {
+            iter.nth(lower - CAP - 1);
+        }
+
         let mut array: ArrayDeque<_, CAP, Wrapping> = ArrayDeque::new();
+        array.fill_back(&mut iter);
         array.extend_back(iter);
         array
     }
 }
 
+impl<'a, T, const CAP: usize> FromIterator<&'a T> for ArrayDeque<T, CAP, Wrapping>
+where
+    T: Copy + 'a,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        iter.into_iter().copied().collect()
+    }
+}
+
 impl<T, const CAP: usize> Clone for ArrayDeque<T, CAP, Wrapping>
 where
     T: Clone,
@@ -618,6 +1191,76 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
         self.set_head_forward();
     }
 
+    /// Push elements from `iter` to the back until the deque is full, writing
+    /// the free run after the head and then the wrapped run from slot 0.
+    fn fill_back<I>(&mut self, mut iter: I)
+    where
+        I: Iterator<Item = T>,
+    {
+        // Writes `len` back once, even if `iter` panics
+        struct SetLenOnDrop<'a> {
+            len: &'a mut usize,
+            local_len: usize,
+        }
+
+        impl<'a> Drop for SetLenOnDrop<'a> {
+            fn drop(&mut self) {
+                *self.len = self.local_len;
+            }
+        }
+
+        let (a, b) = self.uninit_slices();
+        let runs = [(a.as_mut_ptr(), a.len()), (b.as_mut_ptr(), b.len())];
+
+        let mut guard = SetLenOnDrop {
+            local_len: self.len,
+            len: &mut self.len,
+        };
+        for &(start, count) in &runs {
+            let before = guard.local_len;
+            for (offset, element) in (0..count).zip(&mut iter) {
+                unsafe {
+                    (*start.add(offset)).write(element);
+                }
+                guard.local_len += 1;
+            }
+            if guard.local_len - before < count {
+                return;
+            }
+        }
+    }
+
+    /// Bitwise copy `src` into the free slots after the back, and grow the
+    /// deque over them.
+    ///
+    /// `src` must fit, and its elements must not be dropped anywhere else.
+    unsafe fn copy_to_back(&mut self, src: &[T]) {
+        let (a, b) = self.uninit_slices();
+        debug_assert!(src.len() <= a.len() + b.len());
+        let first = cmp::min(src.len(), a.len());
+        ptr::copy_nonoverlapping(src.as_ptr(), a.as_mut_ptr().cast(), first);
+        ptr::copy_nonoverlapping(
+            src.as_ptr().add(first),
+            b.as_mut_ptr().cast(),
+            src.len() - first,
+        );
+        self.set_len(self.len() + src.len());
+    }
+
+    /// Move all the elements of `other` to the back, leaving `other` empty.
+    ///
+    /// There must be room for all of them.
+    fn move_back_from(&mut self, other: &mut Self) {
+        debug_assert!(other.len() <= CAP - self.len());
+
+        let (a, b) = other.as_slices();
+        unsafe {
+            self.copy_to_back(a);
+            self.copy_to_back(b);
+            other.set_len(0);
+        }
+    }
+
     #[allow(unused_unsafe)]
     #[inline]
     unsafe fn insert_unchecked(&mut self, index: usize, element: T) {
@@ -692,11 +1335,14 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
                     //       M                             M
 
                     let tail = self.tail();
-                    let new_tail = Self::wrap_sub(self.tail(), 1);
-
-                    self.copy(new_tail, tail, 1);
-                    // Already moved the tail, so we only copy `index - 1` elements.
-                    self.copy(tail, tail + 1, index - 1);
+                    if tail == 0 {
+                        // The front element wraps around to the end of the buffer,
+                        // the rest shift down within the front run.
+                        self.copy(CAP - 1, 0, 1);
+                        self.as_uninit_slice_mut()[..idx].rotate_left(1);
+                    } else {
+                        self.as_uninit_slice_mut()[tail - 1..idx].rotate_left(1);
+                    }
 
                     self.set_tail_backward();
                 }
@@ -713,7 +1359,7 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
                     //                       M M M
 
                     let head = self.head();
-                    self.copy(idx + 1, idx, head - idx);
+                    self.as_uninit_slice_mut()[idx..=head].rotate_right(1);
 
                     self.set_head_forward();
                 }
@@ -860,11 +1506,7 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
     unsafe fn wrap_copy(&mut self, dst: usize, src: usize, len: usize) {
         #[allow(dead_code)]
         fn diff(a: usize, b: usize) -> usize {
-            if a <= b {
-                b - a
-            } else {
-                a - b
-            }
+            cmp::max(a, b) - cmp::min(a, b)
         }
         debug_assert!(
             cmp::min(diff(dst, src), CAP - diff(dst, src)) + len <= CAP,
@@ -1011,6 +1653,91 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
         }
     }
 
+    /// Creates an `ArrayDeque` holding a copy of the first `capacity()`
+    /// elements of `src`.
+    ///
+    /// Elements of `src` that don't fit are silently ignored, regardless of
+    /// the behavior of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = ArrayDeque::from_slice_truncate(&[1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(buf, [1, 2, 3].into());
+    /// ```
+    pub fn from_slice_truncate(src: &[T]) -> Self
+    where
+        T: Copy,
+    {
+        let mut deque = Self::new();
+        let len = cmp::min(src.len(), CAP);
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), deque.ptr_mut(), len);
+            deque.set_len(len);
+        }
+        deque
+    }
+
+    /// Creates an `ArrayDeque` from the items of an iterator, failing if there
+    /// are more than `capacity()` of them.
+    ///
+    /// Unlike `collect`, which stops or wraps around when the deque is full,
+    /// return `Err(CapacityError)` if the iterator yields more items than
+    /// fit. At most `capacity() + 1` items are pulled from the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf = ArrayDeque::<_, 3>::from_iter_within_capacity(0..3).unwrap();
+    /// assert_eq!(buf, [0, 1, 2].into());
+    ///
+    /// assert!(ArrayDeque::<_, 3>::from_iter_within_capacity(0..4).is_err());
+    /// ```
+    pub fn from_iter_within_capacity<I>(iter: I) -> Result<Self, CapacityError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let mut deque = Self::new();
+        deque.fill_back(&mut iter);
+        match iter.next() {
+            Some(_) => Err(CapacityError { element: () }),
+            None => Ok(deque),
+        }
+    }
+
+    /// Converts the deque to another behavior, keeping the elements in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::{ArrayDeque, Wrapping};
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// buf.extend_back([1, 2]);
+    ///
+    /// let mut buf = buf.into_behavior::<Wrapping>();
+    /// assert_eq!(buf.push_back(3), Some(1));
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(1)` time.
+    pub fn into_behavior<NewB: Behavior>(self) -> ArrayDeque<T, CAP, NewB> {
+        let this = mem::ManuallyDrop::new(self);
+        ArrayDeque {
+            xs: unsafe { ptr::read(&this.xs) },
+            tail: this.tail,
+            len: this.len,
+            marker: marker::PhantomData,
+        }
+    }
+
     /// Return the capacity of the `ArrayDeque`.
     ///
     /// # Examples
@@ -1043,7 +1770,7 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
     /// assert_eq!(buf.len(), 1);
     /// ```
     #[inline]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.len
     }
 
@@ -1063,7 +1790,7 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
     /// assert!(!buf.is_empty());
     /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
@@ -1077,173 +1804,233 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
         unsafe { std::slice::from_raw_parts_mut(self.xs.as_mut_ptr().cast(), CAP) }
     }
 
-    /// Returns true if the buffer is full.
+    /// Returns the free slots right after the back of the deque, as a slice
+    /// of `MaybeUninit<T>`.
+    ///
+    /// Only the contiguous run of free slots starting at the back is
+    /// returned, so the slice can be shorter than `capacity() - len()` when
+    /// the free space wraps around the end of the buffer. Writing to the
+    /// slice doesn't change the length of the deque.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    /// use std::mem::MaybeUninit;
     ///
-    /// assert!(!buf.is_full());
+    /// use arraydeque::ArrayDeque;
     ///
-    /// buf.push_back(1);
+    /// let mut buf: ArrayDeque<u8, 4> = [1].into();
     ///
-    /// assert!(buf.is_full());
+    /// let spare = buf.spare_capacity_mut();
+    /// assert_eq!(spare.len(), 3);
+    /// spare[0] = MaybeUninit::new(2);
     /// ```
-    #[inline]
-    pub fn is_full(&self) -> bool {
-        self.len() == self.capacity()
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self.uninit_slices().0
     }
 
-    /// Returns `true` if the `ArrayDeque` contains an element equal to the
-    /// given value.
+    /// Returns the free slots right before the front of the deque, as a slice
+    /// of `MaybeUninit<T>`.
+    ///
+    /// Only the contiguous run of free slots ending at the front is returned,
+    /// so the slice can be shorter than `capacity() - len()` when the free
+    /// space wraps around the start of the buffer. The front of the deque
+    /// comes right after the last slot of the slice. Writing to the slice
+    /// doesn't change the length of the deque.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
+    /// use std::mem::MaybeUninit;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// use arraydeque::ArrayDeque;
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let mut buf: ArrayDeque<u8, 4> = [1].into();
     ///
-    /// assert_eq!(buf.contains(&1), true);
-    /// assert_eq!(buf.contains(&3), false);
+    /// let spare = buf.spare_capacity_front_mut();
+    /// assert_eq!(spare.len(), 3);
+    /// spare[2] = MaybeUninit::new(0);
     /// ```
-    pub fn contains(&self, x: &T) -> bool
-    where
-        T: PartialEq<T>,
-    {
-        let (a, b) = self.as_slices();
-        a.contains(x) || b.contains(x)
+    pub fn spare_capacity_front_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        // A front at slot 0 is preceded by the end of the buffer
+        let end = if self.tail() == 0 { CAP } else { self.tail() };
+        let start = end.saturating_sub(CAP - self.len());
+        &mut self.as_uninit_slice_mut()[start..end]
     }
 
-    /// Provides a reference to the front element, or `None` if the sequence is
-    /// empty.
+    /// Returns all the free slots of the deque as a pair of slices of
+    /// `MaybeUninit<T>`, in the order in which `push_back` would fill them.
+    ///
+    /// The first slice starts right after the back, as returned by
+    /// [`spare_capacity_mut`](#method.spare_capacity_mut), and the second is
+    /// only non-empty when the free space wraps around the end of the buffer.
+    /// Together they hold `capacity() - len()` slots. Writing to the slices
+    /// doesn't change the length of the deque.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
-    /// assert_eq!(buf.front(), None);
-    ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let mut buf: ArrayDeque<u8, 4> = [1, 2].into();
+    /// buf.pop_front();
     ///
-    /// assert_eq!(buf.front(), Some(&1));
+    /// let (a, b) = buf.uninit_slices();
+    /// assert_eq!((a.len(), b.len()), (2, 1));
     /// ```
-    pub fn front(&self) -> Option<&T> {
-        if !self.is_empty() {
-            Some(&self[0])
-        } else {
-            None
-        }
+    pub fn uninit_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let head = self.head();
+        let free = CAP - self.len();
+        let first = cmp::min(free, CAP - head);
+        let (wrapped, back) = self.as_uninit_slice_mut().split_at_mut(head);
+        (&mut back[..first], &mut wrapped[..free - first])
     }
 
-    /// Provides a mutable reference to the front element, or `None` if the
-    /// sequence is empty.
+    /// Appends the first `n` slots of
+    /// [`spare_capacity_mut`](#method.spare_capacity_mut) to the back of the
+    /// deque.
+    ///
+    /// # Safety
+    ///
+    /// The first `n` slots returned by `spare_capacity_mut` must have been
+    /// initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of `spare_capacity_mut()`.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::mem::MaybeUninit;
+    ///
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
-    /// assert_eq!(buf.front_mut(), None);
+    /// let mut buf: ArrayDeque<u8, 4> = [1].into();
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let spare = buf.spare_capacity_mut();
+    /// spare[0] = MaybeUninit::new(2);
+    /// spare[1] = MaybeUninit::new(3);
+    /// unsafe {
+    ///     buf.commit_back(2);
+    /// }
     ///
-    /// assert_eq!(buf.front_mut(), Some(&mut 1));
+    /// assert_eq!(buf, [1, 2, 3].into());
     /// ```
-    pub fn front_mut(&mut self) -> Option<&mut T> {
-        if !self.is_empty() {
-            Some(&mut self[0])
-        } else {
-            None
-        }
+    #[track_caller]
+    pub unsafe fn commit_back(&mut self, n: usize) {
+        assert!(
+            n <= self.spare_capacity_mut().len(),
+            "`n` exceeds the spare capacity"
+        );
+        self.set_len(self.len() + n);
     }
 
-    /// Provides a reference to the back element, or `None` if the sequence is
-    /// empty.
+    /// Prepends the last `n` slots of
+    /// [`spare_capacity_front_mut`](#method.spare_capacity_front_mut) to the
+    /// front of the deque.
+    ///
+    /// # Safety
+    ///
+    /// The last `n` slots returned by `spare_capacity_front_mut` must have
+    /// been initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the length of
+    /// `spare_capacity_front_mut()`.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::mem::MaybeUninit;
+    ///
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<u8, 4> = [3].into();
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let spare = buf.spare_capacity_front_mut();
+    /// let len = spare.len();
+    /// spare[len - 2] = MaybeUninit::new(1);
+    /// spare[len - 1] = MaybeUninit::new(2);
+    /// unsafe {
+    ///     buf.commit_front(2);
+    /// }
     ///
-    /// assert_eq!(buf.back(), Some(&2));
+    /// assert_eq!(buf, [1, 2, 3].into());
     /// ```
-    pub fn back(&self) -> Option<&T> {
-        if !self.is_empty() {
-            Some(&self[self.len() - 1])
-        } else {
-            None
-        }
+    #[track_caller]
+    pub unsafe fn commit_front(&mut self, n: usize) {
+        assert!(
+            n <= self.spare_capacity_front_mut().len(),
+            "`n` exceeds the spare capacity"
+        );
+        self.set_tail(Self::wrap_sub(self.tail(), n));
+        self.set_len(self.len() + n);
     }
 
-    /// Provides a mutable reference to the back element, or `None` if the
-    /// sequence is empty.
+    /// Returns true if the buffer is full.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    ///
+    /// assert!(!buf.is_full());
     ///
     /// buf.push_back(1);
-    /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.back_mut(), Some(&mut 2));
+    /// assert!(buf.is_full());
     /// ```
-    pub fn back_mut(&mut self) -> Option<&mut T> {
-        let len = self.len();
-        if !self.is_empty() {
-            Some(&mut self[len - 1])
-        } else {
-            None
-        }
+    ///
+    /// Like `len`, `is_empty` and `capacity`, this can be called from a
+    /// `const fn`:
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// const fn has_room<const CAP: usize>(buf: &ArrayDeque<u8, CAP>, n: usize) -> bool {
+    ///     !buf.is_full() && buf.len() + n <= buf.capacity()
+    /// }
+    ///
+    /// assert!(has_room(&ArrayDeque::<u8, 4>::new(), 4));
+    /// ```
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len() == self.capacity()
     }
 
-    /// Retrieves an element in the `ArrayDeque` by index.
+    /// Returns `true` if the `ArrayDeque` contains an element equal to the
+    /// given value.
     ///
-    /// Element at index 0 is the front of the queue.
+    /// Each of the two contiguous halves is searched with the slice `contains`,
+    /// which the standard library implements with `memchr` for byte elements.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
     /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.get(1), Some(&1));
+    /// assert_eq!(buf.contains(&1), true);
+    /// assert_eq!(buf.contains(&3), false);
     /// ```
-    #[inline]
-    pub fn get(&self, index: usize) -> Option<&T> {
-        if index < self.len() {
-            let idx = Self::wrap_add(self.tail(), index);
-            unsafe { Some(&*self.ptr().add(idx)) }
-        } else {
-            None
-        }
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq<T>,
+    {
+        let (a, b) = self.as_slices();
+        a.contains(x) || b.contains(x)
     }
 
-    /// Retrieves an element in the `ArrayDeque` mutably by index.
+    /// Returns the index of the first element that satisfies the predicate,
+    /// or `None` if no element does.
     ///
     /// Element at index 0 is the front of the queue.
     ///
@@ -1252,1539 +2039,6033 @@ impl<T, const CAP: usize, B: Behavior> ArrayDeque<T, CAP, B> {
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let buf: ArrayDeque<_, 4> = [1, 2, 3, 4].into();
     ///
-    /// assert_eq!(buf.get_mut(1), Some(&mut 1));
+    /// assert_eq!(buf.position(|&x| x % 2 == 0), Some(1));
+    /// assert_eq!(buf.position(|&x| x > 4), None);
     /// ```
-    #[inline]
-    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        if index < self.len() {
-            let idx = Self::wrap_add(self.tail(), index);
-            unsafe { Some(&mut *self.ptr_mut().add(idx)) }
-        } else {
-            None
-        }
+    pub fn position<P>(&self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        a.iter()
+            .position(&mut pred)
+            .or_else(|| b.iter().position(pred).map(|i| a.len() + i))
     }
 
-    /// Returns a front-to-back iterator.
+    /// Returns the index of the last element that satisfies the predicate,
+    /// or `None` if no element does.
+    ///
+    /// Element at index 0 is the front of the queue.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// let expected = [0, 1, 2];
+    /// let buf: ArrayDeque<_, 4> = [1, 2, 3, 4].into();
     ///
-    /// assert!(buf.iter().eq(expected.iter()));
+    /// assert_eq!(buf.rposition(|&x| x % 2 == 1), Some(2));
+    /// assert_eq!(buf.rposition(|&x| x > 4), None);
     /// ```
-    #[inline]
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            tail: self.tail(),
-            len: self.len(),
-            ring: self.as_uninit_slice(),
-        }
+    pub fn rposition<P>(&self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        b.iter()
+            .rposition(&mut pred)
+            .map(|i| a.len() + i)
+            .or_else(|| a.iter().rposition(pred))
     }
 
-    /// Returns a front-to-back iterator that returns mutable references.
+    /// Returns a reference to the first element, front-to-back, that
+    /// satisfies the predicate, or `None` if there is none.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<usize, 3> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// let mut expected = [0, 1, 2];
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.extend_back([3, 4]);
+    /// buf.extend_front([2, 1]);
     ///
-    /// assert!(buf.iter_mut().eq(expected.iter_mut()));
+    /// assert_eq!(buf.find(|&x| x > 2), Some(&3));
+    /// assert_eq!(buf.find(|&x| x > 4), None);
     /// ```
-    #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut {
-            tail: self.tail(),
-            len: self.len(),
-            ring: self.as_uninit_slice_mut(),
-        }
+    pub fn find<P>(&self, mut pred: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        a.iter()
+            .find(|x| pred(x))
+            .or_else(|| b.iter().find(|x| pred(x)))
     }
 
-    /// Make the buffer contiguous
+    /// Returns `true` if both deques hold equal elements in the same order,
+    /// whatever their capacities and behaviors.
     ///
-    /// The linearization may be required when interacting with external
-    /// interfaces requiring contiguous slices.
+    /// `PartialEq` only compares deques of the same type, as a generic impl
+    /// would break type inference for comparisons like `buf == x.into()`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<isize, 10> = ArrayDeque::new();
-    /// buf.extend_back([1, 2, 3]);
-    /// buf.extend_front([-1, -2, -3]);
+    /// use arraydeque::{ArrayDeque, Wrapping};
     ///
-    /// buf.linearize();
+    /// let small: ArrayDeque<_, 4> = [1, 2, 3].into();
+    /// let large: ArrayDeque<_, 16, Wrapping> = [1, 2, 3].into();
     ///
-    /// assert_eq!(buf.as_slices().1.len(), 0);
+    /// assert!(small.contents_eq(&large));
     /// ```
-    ///
-    /// # Complexity
-    ///
-    /// Takes `O(len())` time and no extra space.
-    pub fn linearize(&mut self) {
-        if self.is_contiguous() {
-            return;
+    pub fn contents_eq<const CAP2: usize, B2: Behavior>(
+        &self,
+        other: &ArrayDeque<T, CAP2, B2>,
+    ) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.len() != other.len() {
+            return false;
         }
+        let (sa, sb) = self.as_slices();
+        let (oa, ob) = other.as_slices();
+        match sa.len().cmp(&oa.len()) {
+            // Same split, which includes both deques being contiguous: two
+            // slice comparisons, each a single `memcmp` for primitives
+            Ordering::Equal => sa == oa && sb == ob,
+            Ordering::Less => {
+                // Always divisible in three sections, for example:
+                // self:  [a b c|d e f]
+                // other: [0 1 2 3|4 5]
+                // front = 3, mid = 1,
+                // [a b c] == [0 1 2] && [d] == [3] && [e f] == [4 5]
+                let front = sa.len();
+                let mid = oa.len() - front;
 
-        let tail = self.tail();
-        let len = self.len();
-        let mut new_tail = tail;
-        let mut dst: usize = 0;
-
-        while dst < len {
-            let mut src = new_tail;
-
-            while src < CAP && dst < len {
-                if dst == new_tail {
-                    new_tail = src;
-                }
-
-                let xs = self.ptr_mut();
-                unsafe {
-                    ptr::swap(xs.add(dst), xs.add(src));
-                }
-
-                dst += 1;
-                src += 1;
+                let (oa_front, oa_mid) = oa.split_at(front);
+                let (sb_mid, sb_back) = sb.split_at(mid);
+                debug_assert_eq!(sa.len(), oa_front.len());
+                debug_assert_eq!(sb_mid.len(), oa_mid.len());
+                debug_assert_eq!(sb_back.len(), ob.len());
+                sa == oa_front && sb_mid == oa_mid && sb_back == ob
             }
-        }
+            Ordering::Greater => {
+                let front = oa.len();
+                let mid = sa.len() - front;
 
-        unsafe {
-            self.set_tail(0);
+                let (sa_front, sa_mid) = sa.split_at(front);
+                let (ob_mid, ob_back) = ob.split_at(mid);
+                debug_assert_eq!(sa_front.len(), oa.len());
+                debug_assert_eq!(sa_mid.len(), ob_mid.len());
+                debug_assert_eq!(sb.len(), ob_back.len());
+                sa_front == oa && sa_mid == ob_mid && sb == ob_back
+            }
         }
     }
 
-    /// Removes the first element and returns it, or `None` if the sequence is
-    /// empty.
+    /// Returns the index of the first element equal to `value`, or `None` if
+    /// there is none.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// let buf: ArrayDeque<_, 4> = [1, 0, 1, 2].into();
     ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// assert_eq!(buf.index_of(&1), Some(0));
+    /// assert_eq!(buf.index_of(&2), Some(3));
+    /// assert_eq!(buf.index_of(&3), None);
+    /// ```
+    pub fn index_of(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.position(|x| x == value)
+    }
+
+    /// Returns the number of elements equal to `value`.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(buf.pop_front(), Some(1));
-    /// assert_eq!(buf.pop_front(), Some(2));
-    /// assert_eq!(buf.pop_front(), None);
     /// ```
-    pub fn pop_front(&mut self) -> Option<T> {
-        if self.is_empty() {
-            return None;
-        }
-        unsafe {
-            let tail = self.tail();
-            self.set_tail_forward();
-            Some(self.buffer_read(tail))
-        }
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 4> = [1, 0, 1, 2].into();
+    ///
+    /// assert_eq!(buf.count_eq(&1), 2);
+    /// assert_eq!(buf.count_eq(&3), 0);
+    /// ```
+    pub fn count_eq(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let (a, b) = self.as_slices();
+        let count = |s: &[T]| s.iter().filter(|x| *x == value).count();
+        count(a) + count(b)
     }
 
-    /// Removes the last element from a buffer and returns it, or `None` if
-    /// it is empty.
+    /// Sums the elements of the `ArrayDeque`.
+    ///
+    /// Each of the two contiguous halves is summed in a single pass.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
-    /// assert_eq!(buf.pop_back(), None);
-    ///
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let buf: ArrayDeque<_, 4> = [1, 2, 3, 4].into();
     ///
-    /// assert_eq!(buf.pop_back(), Some(2));
-    /// assert_eq!(buf.pop_back(), Some(1));
+    /// assert_eq!(buf.sum(), 10);
     /// ```
-    pub fn pop_back(&mut self) -> Option<T> {
-        if self.is_empty() {
-            return None;
-        }
-        unsafe {
-            self.set_head_backward();
-            let head = self.head();
-            Some(self.buffer_read(head))
-        }
+    pub fn sum(&self) -> T
+    where
+        T: Sum + Copy,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).copied().sum()
     }
 
-    /// Clears the buffer, removing all values.
+    /// Multiplies the elements of the `ArrayDeque`.
+    ///
+    /// Each of the two contiguous halves is multiplied in a single pass.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
-    ///
-    /// buf.push_back(1);
-    /// buf.clear();
+    /// let buf: ArrayDeque<_, 4> = [1, 2, 3, 4].into();
     ///
-    /// assert!(buf.is_empty());
+    /// assert_eq!(buf.product(), 24);
     /// ```
-    #[inline]
-    pub fn clear(&mut self) {
-        self.drain(..);
+    pub fn product(&self) -> T
+    where
+        T: Product + Copy,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).copied().product()
     }
 
-    /// Create a draining iterator that removes the specified range in the
-    /// `ArrayDeque` and yields the removed items.
-    ///
-    /// Note 1: The element range is removed even if the iterator is not
-    /// consumed until the end.
-    ///
-    /// Note 2: It is unspecified how many elements are removed from the deque,
-    /// if the `Drain` value is not dropped, but the borrow it holds expires
-    /// (eg. due to mem::forget).
+    /// Returns a reference to the minimum element, or `None` if the deque is
+    /// empty.
     ///
-    /// # Panics
+    /// If several elements are equally minimum, the first one is returned,
+    /// matching `Iterator::min`.
     ///
-    /// Panics if the starting point is greater than the end point or if
-    /// the end point is greater than the length of the deque.
+    /// This is not called `min` because `Ord::min` would take precedence over
+    /// it in method call syntax.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// let buf: ArrayDeque<_, 4> = [3, 1, 4, 1].into();
     ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// assert_eq!(buf.min_element(), Some(&1));
+    /// ```
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).min()
+    }
+
+    /// Returns a reference to the maximum element, or `None` if the deque is
+    /// empty.
     ///
-    /// {
-    ///     let drain = buf.drain(2..);
-    ///     assert!([2].into_iter().eq(drain));
-    /// }
+    /// If several elements are equally maximum, the last one is returned,
+    /// matching `Iterator::max`.
     ///
-    /// {
-    ///     let iter = buf.iter();
-    ///     assert!([0, 1].iter().eq(iter));
-    /// }
+    /// This is not called `max` because `Ord::max` would take precedence over
+    /// it in method call syntax.
+    ///
+    /// # Examples
     ///
-    /// // A full range clears all contents
-    /// buf.drain(..);
-    /// assert!(buf.is_empty());
     /// ```
-    #[track_caller]
-    #[inline]
-    pub fn drain<R>(&mut self, range: R) -> Drain<T, CAP, B>
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 4> = [3, 1, 4, 1].into();
+    ///
+    /// assert_eq!(buf.max_element(), Some(&4));
+    /// ```
+    pub fn max_element(&self) -> Option<&T>
     where
-        R: RangeArgument<usize>,
+        T: Ord,
     {
-        let len = self.len();
-        let start = range.start().unwrap_or(0);
-        let end = range.end().unwrap_or(len);
-        assert!(start <= end, "drain lower bound was too large");
-        assert!(end <= len, "drain upper bound was too large");
-
-        let drain_tail = Self::wrap_add(self.tail(), start);
-        let drain_head = Self::wrap_add(self.tail(), end);
-        let drain_len = end - start;
-
-        unsafe { self.set_len(start) }
-
-        Drain {
-            deque: self as *mut _,
-            after_tail: drain_head,
-            after_len: len - end,
-            iter: Iter {
-                tail: drain_tail,
-                len: drain_len,
-                ring: self.as_uninit_slice_mut(),
-            },
-        }
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).max()
     }
 
-    /// Swaps elements at indices `i` and `j`.
+    /// Returns a reference to the element that gives the minimum value with
+    /// respect to the comparison function, or `None` if the deque is empty.
     ///
-    /// `i` and `j` may be equal.
+    /// If several elements are equally minimum, the first one is returned.
     ///
-    /// Fails if there is no element with either index.
+    /// # Examples
     ///
-    /// Element at index 0 is the front of the queue.
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = [-3_i32, 0, 1].into();
+    ///
+    /// assert_eq!(buf.min_by(|x, y| x.abs().cmp(&y.abs())), Some(&0));
+    /// ```
+    pub fn min_by<F>(&self, mut compare: F) -> Option<&T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).min_by(|x, y| compare(x, y))
+    }
+
+    /// Returns a reference to the element that gives the maximum value with
+    /// respect to the comparison function, or `None` if the deque is empty.
+    ///
+    /// If several elements are equally maximum, the last one is returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// let buf: ArrayDeque<_, 3> = [-3_i32, 0, 1].into();
     ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// assert_eq!(buf.max_by(|x, y| x.abs().cmp(&y.abs())), Some(&-3));
+    /// ```
+    pub fn max_by<F>(&self, mut compare: F) -> Option<&T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).max_by(|x, y| compare(x, y))
+    }
+
+    /// Returns a reference to the element that gives the minimum value from
+    /// the specified function, or `None` if the deque is empty.
     ///
-    /// buf.swap(0, 2);
+    /// If several elements are equally minimum, the first one is returned.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!(buf, [2, 1, 0].into());
     /// ```
-    #[track_caller]
-    #[inline]
-    pub fn swap(&mut self, i: usize, j: usize) {
-        assert!(i < self.len());
-        assert!(j < self.len());
-        let ri = Self::wrap_add(self.tail(), i);
-        let rj = Self::wrap_add(self.tail(), j);
-        let xs = self.ptr_mut();
-        unsafe { ptr::swap(xs.add(ri), xs.add(rj)) }
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = [-3_i32, 0, 1].into();
+    ///
+    /// assert_eq!(buf.min_by_key(|x| x.abs()), Some(&0));
+    /// ```
+    pub fn min_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).min_by_key(|x| f(x))
     }
 
-    /// Removes an element from anywhere in the `ArrayDeque` and returns it, replacing it with the
-    /// last element.
+    /// Returns a reference to the element that gives the maximum value from
+    /// the specified function, or `None` if the deque is empty.
     ///
-    /// This does not preserve ordering, but is O(1).
+    /// If several elements are equally maximum, the last one is returned.
     ///
-    /// Returns `None` if `index` is out of bounds.
+    /// # Examples
     ///
-    /// Element at index 0 is the front of the queue.
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = [-3_i32, 0, 1].into();
+    ///
+    /// assert_eq!(buf.max_by_key(|x| x.abs()), Some(&-3));
+    /// ```
+    pub fn max_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let (a, b) = self.as_slices();
+        a.iter().chain(b).max_by_key(|x| f(x))
+    }
+
+    /// Returns `true` if the elements are sorted by the key extracted with
+    /// `f`, front to back.
+    ///
+    /// An empty deque or a deque with a single element is always sorted.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
-    /// assert_eq!(buf.swap_remove_back(0), None);
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
+    /// let buf: ArrayDeque<_, 3> = [(0, 'c'), (1, 'b'), (1, 'a')].into();
     ///
-    /// assert_eq!(buf.swap_remove_back(0), Some(0));
-    /// assert_eq!(buf, [2, 1].into());
+    /// assert!(buf.is_sorted_by_key(|x| x.0));
+    /// assert!(!buf.is_sorted_by_key(|x| x.1));
     /// ```
-    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
-        let length = self.len();
-        if length > 0 && index < length - 1 {
-            self.swap(index, length - 1);
-        } else if index >= length {
-            return None;
+    pub fn is_sorted_by_key<K, F>(&self, f: F) -> bool
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let mut keys = self.iter().map(f);
+        let mut last = match keys.next() {
+            Some(key) => key,
+            None => return true,
+        };
+        for key in keys {
+            if last > key {
+                return false;
+            }
+            last = key;
         }
-        self.pop_back()
+        true
     }
 
-    /// Removes an element from anywhere in the `ArrayDeque` and returns it,
-    /// replacing it with the first element.
+    /// Returns an adapter that displays the elements front to back, separated
+    /// by `sep`.
     ///
-    /// This does not preserve ordering, but is O(1).
+    /// The elements are written straight to the formatter, without building
+    /// intermediate strings. Formatting options like width and precision are
+    /// applied to each element.
     ///
-    /// Returns `None` if `index` is out of bounds.
+    /// # Examples
     ///
-    /// Element at index 0 is the front of the queue.
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = [2, 3].into();
+    /// buf.push_front(1).unwrap();
+    ///
+    /// assert_eq!(buf.display_joined(", ").to_string(), "1, 2, 3");
+    /// assert_eq!(format!("{:.1}", ArrayDeque::<f32, 2>::from([0.5, 1.0]).display_joined("/")), "0.5/1.0");
+    /// ```
+    pub fn display_joined<'a>(&'a self, sep: &'a str) -> impl fmt::Display + 'a
+    where
+        T: fmt::Display,
+    {
+        DisplayJoined {
+            iter: self.iter(),
+            sep,
+        }
+    }
+
+    /// Provides a reference to the front element, or `None` if the sequence is
+    /// empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
-    /// assert_eq!(buf.swap_remove_back(0), None);
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.front(), None);
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
     /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.swap_remove_front(2), Some(2));
-    /// assert_eq!(buf, [1, 0].into());
+    /// assert_eq!(buf.front(), Some(&1));
     /// ```
-    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
-        let length = self.len();
-        if length > 0 && index < length && index != 0 {
-            self.swap(index, 0);
-        } else if index >= length {
-            return None;
+    pub fn front(&self) -> Option<&T> {
+        if !self.is_empty() {
+            Some(&self[0])
+        } else {
+            None
         }
-        self.pop_front()
     }
 
-    /// Removes and returns the element at `index` from the `ArrayDeque`.
-    /// Whichever end is closer to the removal point will be moved to make
-    /// room, and all the affected elements will be moved to new positions.
-    /// Returns `None` if `index` is out of bounds.
-    ///
-    /// Element at index 0 is the front of the queue.
+    /// Provides a mutable reference to the front element, or `None` if the
+    /// sequence is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use arraydeque::ArrayDeque;
     ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.front_mut(), None);
     ///
-    /// buf.push_back(0);
     /// buf.push_back(1);
     /// buf.push_back(2);
     ///
-    /// assert_eq!(buf.remove(1), Some(1));
-    /// assert_eq!(buf, [0, 2].into());
+    /// assert_eq!(buf.front_mut(), Some(&mut 1));
     /// ```
-    pub fn remove(&mut self, index: usize) -> Option<T> {
-        if self.is_empty() || self.len() <= index {
-            return None;
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if !self.is_empty() {
+            Some(&mut self[0])
+        } else {
+            None
         }
+    }
 
-        // There are three main cases:
+    /// Provides a reference to the back element, or `None` if the sequence is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.back(), Some(&2));
+    /// ```
+    pub fn back(&self) -> Option<&T> {
+        if !self.is_empty() {
+            Some(&self[self.len() - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Provides a mutable reference to the back element, or `None` if the
+    /// sequence is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.back_mut(), Some(&mut 2));
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let len = self.len();
+        if !self.is_empty() {
+            Some(&mut self[len - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves an element in the `ArrayDeque` by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.get(1), Some(&1));
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            let idx = Self::wrap_add(self.tail(), index);
+            unsafe { Some(&*self.ptr().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves an element in the `ArrayDeque` mutably by index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.get_mut(1), Some(&mut 1));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            let idx = Self::wrap_add(self.tail(), index);
+            unsafe { Some(&mut *self.ptr_mut().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the element at `index % len()`, so that any index wraps
+    /// around the elements instead of running out of bounds.
+    ///
+    /// Returns `None` only if the `ArrayDeque` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 4> = [0, 1, 2].into();
+    ///
+    /// assert_eq!(buf.get_wrapping(1), Some(&1));
+    /// assert_eq!(buf.get_wrapping(5), Some(&2));
+    /// assert_eq!(ArrayDeque::<i32, 4>::new().get_wrapping(0), None);
+    /// ```
+    #[inline]
+    pub fn get_wrapping(&self, index: usize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.get(index % self.len())
+    }
+
+    /// Retrieves two distinct elements in the `ArrayDeque` mutably by index.
+    ///
+    /// Return `None` if `i == j` or either index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = [0, 1, 2].into();
+    ///
+    /// if let Some((a, b)) = buf.get_pair_mut(2, 0) {
+    ///     *a += 10;
+    ///     *b += 20;
+    /// }
+    /// assert_eq!(buf, [20, 1, 12].into());
+    ///
+    /// assert!(buf.get_pair_mut(1, 1).is_none());
+    /// assert!(buf.get_pair_mut(0, 3).is_none());
+    /// ```
+    pub fn get_pair_mut(&mut self, i: usize, j: usize) -> Option<(&mut T, &mut T)> {
+        let len = self.len();
+        if i == j || i >= len || j >= len {
+            return None;
+        }
+        let tail = self.tail();
+        let xs = self.ptr_mut();
+        unsafe {
+            Some((
+                &mut *xs.add(Self::wrap_add(tail, i)),
+                &mut *xs.add(Self::wrap_add(tail, j)),
+            ))
+        }
+    }
+
+    /// Returns a front-to-back iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let expected = [0, 1, 2];
+    ///
+    /// assert!(buf.iter().eq(expected.iter()));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            tail: self.tail(),
+            len: self.len(),
+            ring: self.as_uninit_slice(),
+        }
+    }
+
+    /// Returns two front-to-back iterators, over the elements before `mid`
+    /// and over the elements from `mid` on.
+    ///
+    /// No elements are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 5> = [1, 2, 3, 4, 5].into();
+    /// let (front, back) = buf.split_at(2);
+    ///
+    /// assert_eq!(front.sum::<i32>(), 3);
+    /// assert_eq!(back.sum::<i32>(), 12);
+    /// ```
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (Iter<'_, T>, Iter<'_, T>) {
+        assert!(mid <= self.len(), "`mid` out of bounds");
+        let mut back = self.iter();
+        back.advance(mid);
+        let front = Iter {
+            tail: self.tail(),
+            len: mid,
+            ring: self.as_uninit_slice(),
+        };
+        (front, back)
+    }
+
+    /// Returns a front-to-back iterator that returns mutable references.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<usize, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// let mut expected = [0, 1, 2];
+    ///
+    /// assert!(buf.iter_mut().eq(expected.iter_mut()));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            tail: self.tail(),
+            len: self.len(),
+            ring: self.as_uninit_slice_mut(),
+        }
+    }
+
+    /// Returns the first element mutably and an iterator over the rest of the
+    /// elements, or `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [1, 2, 3].into();
+    ///
+    /// if let Some((first, rest)) = buf.split_first_mut() {
+    ///     for x in rest {
+    ///         *first += *x;
+    ///         *x = 0;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(buf, [6, 0, 0].into());
+    /// ```
+    pub fn split_first_mut(&mut self) -> Option<(&mut T, IterMut<'_, T>)> {
+        let mut iter = self.iter_mut();
+        let first = iter.next()?;
+        Some((first, iter))
+    }
+
+    /// Returns the last element mutably and an iterator over the rest of the
+    /// elements, or `None` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [1, 2, 3].into();
+    ///
+    /// if let Some((last, rest)) = buf.split_last_mut() {
+    ///     for x in rest {
+    ///         *last += *x;
+    ///         *x = 0;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(buf, [0, 0, 6].into());
+    /// ```
+    pub fn split_last_mut(&mut self) -> Option<(&mut T, IterMut<'_, T>)> {
+        let mut iter = self.iter_mut();
+        let last = iter.next_back()?;
+        Some((last, iter))
+    }
+
+    /// Returns an iterator that yields the elements front-to-back, repeating
+    /// forever.
+    ///
+    /// The iterator is empty if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = [0, 1, 2].into();
+    ///
+    /// let expected = [0, 1, 2, 0, 1, 2, 0];
+    ///
+    /// assert!(buf.cycle().take(7).eq(expected.iter()));
+    /// ```
+    #[inline]
+    pub fn cycle(&self) -> Cycle<'_, T> {
+        Cycle {
+            ring: self.as_uninit_slice(),
+            tail: self.tail(),
+            len: self.len(),
+            index: 0,
+        }
+    }
+
+    /// Make the buffer contiguous
+    ///
+    /// The linearization may be required when interacting with external
+    /// interfaces requiring contiguous slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<isize, 10> = ArrayDeque::new();
+    /// buf.extend_back([1, 2, 3]);
+    /// buf.extend_front([-1, -2, -3]);
+    ///
+    /// buf.linearize();
+    ///
+    /// assert_eq!(buf.as_slices().1.len(), 0);
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(len())` time and no extra space.
+    pub fn linearize(&mut self) {
+        if self.is_contiguous() {
+            return;
+        }
+
+        let tail = self.tail();
+        let len = self.len();
+        let mut new_tail = tail;
+        let mut dst: usize = 0;
+
+        while dst < len {
+            let mut src = new_tail;
+
+            while src < CAP && dst < len {
+                if dst == new_tail {
+                    new_tail = src;
+                }
+
+                let xs = self.ptr_mut();
+                unsafe {
+                    ptr::swap(xs.add(dst), xs.add(src));
+                }
+
+                dst += 1;
+                src += 1;
+            }
+        }
+
+        unsafe {
+            self.set_tail(0);
+        }
+    }
+
+    /// Move the elements to the start of the underlying buffer.
+    ///
+    /// Unlike [`linearize`](#method.linearize), this also moves data that is
+    /// already contiguous, so that the first element always sits in the first
+    /// slot and all the spare capacity is one run after the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    /// buf.extend_back([1, 2, 3, 4]);
+    /// buf.pop_front();
+    /// buf.pop_front();
+    ///
+    /// buf.compact();
+    /// buf.extend_back([5, 6]);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[3, 4, 5, 6][..], &[][..]));
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(len())` time and no extra space.
+    pub fn compact(&mut self) {
+        if !self.is_contiguous() {
+            self.linearize();
+            return;
+        }
+
+        let tail = self.tail();
+        if tail != 0 {
+            unsafe {
+                self.copy(0, tail, self.len());
+                self.set_tail(0);
+            }
+        }
+    }
+
+    /// Removes the first element and returns it, or `None` if the sequence is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.pop_front(), Some(1));
+    /// assert_eq!(buf.pop_front(), Some(2));
+    /// assert_eq!(buf.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            let tail = self.tail();
+            self.set_tail_forward();
+            Some(self.buffer_read(tail))
+        }
+    }
+
+    /// Removes the last element from a buffer and returns it, or `None` if
+    /// it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    /// assert_eq!(buf.pop_back(), None);
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.pop_back(), Some(2));
+    /// assert_eq!(buf.pop_back(), Some(1));
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            self.set_head_backward();
+            let head = self.head();
+            Some(self.buffer_read(head))
+        }
+    }
+
+    /// Removes the first and the last elements and returns them, as
+    /// `(pop_front(), pop_back())`.
+    ///
+    /// If the deque holds a single element, it is returned as the first
+    /// component and the second is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = [1, 2, 3].into();
+    ///
+    /// assert_eq!(buf.pop_ends(), (Some(1), Some(3)));
+    /// assert_eq!(buf.pop_ends(), (Some(2), None));
+    /// assert_eq!(buf.pop_ends(), (None, None));
+    /// ```
+    pub fn pop_ends(&mut self) -> (Option<T>, Option<T>) {
+        let front = self.pop_front();
+        (front, self.pop_back())
+    }
+
+    /// Clears the buffer, removing all values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 1> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.clear();
+    ///
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        if mem::needs_drop::<T>() {
+            self.drain(..);
+        } else {
+            unsafe {
+                self.set_tail(0);
+                self.set_len(0);
+            }
+        }
+    }
+
+    /// Create a draining iterator that removes the specified range in the
+    /// `ArrayDeque` and yields the removed items.
+    ///
+    /// Note 1: The element range is removed even if the iterator is not
+    /// consumed until the end.
+    ///
+    /// Note 2: The deque is shortened to the elements before the range as soon
+    /// as the `Drain` is created. If the `Drain` value is leaked (eg. due to
+    /// mem::forget), the deque keeps only those elements, and the rest are
+    /// leaked without ever being dropped or reachable again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// {
+    ///     let drain = buf.drain(2..);
+    ///     assert!([2].into_iter().eq(drain));
+    /// }
+    ///
+    /// {
+    ///     let iter = buf.iter();
+    ///     assert!([0, 1].iter().eq(iter));
+    /// }
+    ///
+    /// // A full range clears all contents
+    /// buf.drain(..);
+    /// assert!(buf.is_empty());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP, B>
+    where
+        R: RangeArgument<usize>,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "drain lower bound was too large");
+        assert!(end <= len, "drain upper bound was too large");
+
+        let drain_tail = Self::wrap_add(self.tail(), start);
+        let drain_head = Self::wrap_add(self.tail(), end);
+        let drain_len = end - start;
+
+        unsafe { self.set_len(start) }
+
+        Drain {
+            deque: self as *mut _,
+            after_tail: drain_head,
+            after_len: len - end,
+            iter: Iter {
+                tail: drain_tail,
+                len: drain_len,
+                ring: self.as_uninit_slice_mut(),
+            },
+        }
+    }
+
+    /// Create a draining iterator that removes the first `n` elements of the
+    /// `ArrayDeque` and yields the removed items.
+    ///
+    /// `n` is clamped to the length of the deque, so this never panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+    ///
+    /// assert!([0, 1].into_iter().eq(buf.drain_front(2)));
+    /// assert_eq!(buf, [2, 3].into());
+    ///
+    /// assert!([2, 3].into_iter().eq(buf.drain_front(5)));
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_front(&mut self, n: usize) -> Drain<'_, T, CAP, B> {
+        let n = cmp::min(n, self.len());
+        self.drain(..n)
+    }
+
+    /// Create a draining iterator that removes the last `n` elements of the
+    /// `ArrayDeque` and yields the removed items.
+    ///
+    /// `n` is clamped to the length of the deque, so this never panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+    ///
+    /// assert!([2, 3].into_iter().eq(buf.drain_back(2)));
+    /// assert_eq!(buf, [0, 1].into());
+    ///
+    /// assert!([0, 1].into_iter().eq(buf.drain_back(5)));
+    /// assert!(buf.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_back(&mut self, n: usize) -> Drain<'_, T, CAP, B> {
+        let len = self.len();
+        let n = cmp::min(n, len);
+        self.drain(len - n..)
+    }
+
+    /// Modifies the deque in-place so that `len()` is equal to `new_len`,
+    /// adding or removing elements at the front.
+    ///
+    /// Growing prepends clones of `value`, and shrinking drops elements from
+    /// the front. `new_len` is clamped to the capacity, so growing never
+    /// pushes out existing elements, whatever the behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [1, 2].into();
+    ///
+    /// buf.resize_front(3, 0);
+    /// assert_eq!(buf, [0, 1, 2].into());
+    ///
+    /// buf.resize_front(1, 0);
+    /// assert_eq!(buf, [2].into());
+    ///
+    /// buf.resize_front(10, 0);
+    /// assert_eq!(buf, [0, 0, 0, 2].into());
+    /// ```
+    pub fn resize_front(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let new_len = cmp::min(new_len, CAP);
+        let len = self.len();
+        if new_len <= len {
+            self.drain_front(len - new_len);
+        } else {
+            for _ in len..new_len {
+                unsafe {
+                    self.push_front_unchecked(value.clone());
+                }
+            }
+        }
+    }
+
+    /// Pushes the values returned by calling `f` onto the back until the deque
+    /// is full.
+    ///
+    /// The existing elements are left in place, whatever the behavior, and
+    /// `f` is called once for each free slot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [7].into();
+    /// let mut next = 0;
+    ///
+    /// buf.fill_to_capacity_with(|| {
+    ///     next += 1;
+    ///     next
+    /// });
+    ///
+    /// assert_eq!(buf, [7, 1, 2, 3].into());
+    /// ```
+    pub fn fill_to_capacity_with<F>(&mut self, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.fill_back(std::iter::repeat_with(f));
+    }
+
+    /// Removes the specified range from the `ArrayDeque`, dropping the removed
+    /// elements in place.
+    ///
+    /// This is the same as dropping the iterator returned by `drain`. Returns
+    /// the number of elements removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+    ///
+    /// assert_eq!(buf.remove_range(1..3), 2);
+    /// assert_eq!(buf, [0, 3].into());
+    /// ```
+    #[track_caller]
+    pub fn remove_range<R>(&mut self, range: R) -> usize
+    where
+        R: RangeArgument<usize>,
+    {
+        self.drain(range).len()
+    }
+
+    /// Swaps elements at indices `i` and `j`.
+    ///
+    /// `i` and `j` may be equal.
+    ///
+    /// Fails if there is no element with either index.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// buf.swap(0, 2);
+    ///
+    /// assert_eq!(buf, [2, 1, 0].into());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn swap(&mut self, i: usize, j: usize) {
+        assert!(i < self.len());
+        assert!(j < self.len());
+        let ri = Self::wrap_add(self.tail(), i);
+        let rj = Self::wrap_add(self.tail(), j);
+        let xs = self.ptr_mut();
+        unsafe { ptr::swap(xs.add(ri), xs.add(rj)) }
+    }
+
+    /// Reverses the order of the elements in the specified range, in place.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [0, 1, 2, 3, 4].into();
+    ///
+    /// buf.reverse_range(1..4);
+    ///
+    /// assert_eq!(buf, [0, 3, 2, 1, 4].into());
+    /// ```
+    #[track_caller]
+    pub fn reverse_range<R>(&mut self, range: R)
+    where
+        R: RangeArgument<usize>,
+    {
+        let len = self.len();
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(len);
+        assert!(start <= end, "reverse_range lower bound was too large");
+        assert!(end <= len, "reverse_range upper bound was too large");
+
+        let (mut i, mut j) = (start, end);
+        while i + 1 < j {
+            j -= 1;
+            self.swap(i, j);
+            i += 1;
+        }
+    }
+
+    /// Rotates the deque to the left until its first element is equal to
+    /// `value`.
+    ///
+    /// Return `false` and leave the deque unchanged if no element is equal to
+    /// `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [0, 1, 2, 3].into();
+    ///
+    /// assert!(buf.rotate_to(&2));
+    /// assert_eq!(buf, [2, 3, 0, 1].into());
+    ///
+    /// assert!(!buf.rotate_to(&4));
+    /// assert_eq!(buf, [2, 3, 0, 1].into());
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(len())` time to find `value`, and moves at most
+    /// `len() / 2` elements.
+    pub fn rotate_to(&mut self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let mid = match self.position(|x| x == value) {
+            Some(mid) => mid,
+            None => return false,
+        };
+
+        // Move whichever side is shorter around to the other end
+        let len = self.len();
+        if mid <= len - mid {
+            for _ in 0..mid {
+                unsafe {
+                    let tail = self.tail();
+                    self.set_tail_forward();
+                    let element = self.buffer_read(tail);
+                    self.push_back_unchecked(element);
+                }
+            }
+        } else {
+            for _ in mid..len {
+                unsafe {
+                    self.set_head_backward();
+                    let head = self.head();
+                    let element = self.buffer_read(head);
+                    self.push_front_unchecked(element);
+                }
+            }
+        }
+        true
+    }
+
+    /// Removes an element from anywhere in the `ArrayDeque` and returns it, replacing it with the
+    /// last element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// assert_eq!(buf.swap_remove_back(0), None);
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.swap_remove_back(0), Some(0));
+    /// assert_eq!(buf, [2, 1].into());
+    /// ```
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        let length = self.len();
+        if length > 0 && index < length - 1 {
+            self.swap(index, length - 1);
+        } else if index >= length {
+            return None;
+        }
+        self.pop_back()
+    }
+
+    /// Removes an element from anywhere in the `ArrayDeque` and returns it,
+    /// replacing it with the first element.
+    ///
+    /// This does not preserve ordering, but is O(1).
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    /// assert_eq!(buf.swap_remove_back(0), None);
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.swap_remove_front(2), Some(2));
+    /// assert_eq!(buf, [1, 0].into());
+    /// ```
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        let length = self.len();
+        if length > 0 && index < length && index != 0 {
+            self.swap(index, 0);
+        } else if index >= length {
+            return None;
+        }
+        self.pop_front()
+    }
+
+    /// Removes and returns the element at `index` from the `ArrayDeque`.
+    /// Whichever end is closer to the removal point will be moved to make
+    /// room, and all the affected elements will be moved to new positions.
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// assert_eq!(buf.remove(1), Some(1));
+    /// assert_eq!(buf, [0, 2].into());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if self.is_empty() || self.len() <= index {
+            return None;
+        }
+
+        // There are three main cases:
         //  Elements are contiguous
         //  Elements are discontiguous and the removal is in the tail section
         //  Elements are discontiguous and the removal is in the head section
         //      - special case when elements are technically contiguous,
         //        but self.head = 0
         //
-        // For each of those there are two more cases:
-        //  Insert is closer to tail
-        //  Insert is closer to head
+        // For each of those there are two more cases:
+        //  Insert is closer to tail
+        //  Insert is closer to head
+        //
+        // Key: H - self.head
+        //      T - self.tail
+        //      o - Valid element
+        //      x - Element marked for removal
+        //      R - Indicates element that is being removed
+        //      M - Indicates element was moved
+
+        let idx = Self::wrap_add(self.tail(), index);
+
+        let elem = unsafe { Some(self.buffer_read(idx)) };
+
+        let distance_to_tail = index;
+        let distance_to_head = self.len() - index;
+
+        let contiguous = self.is_contiguous();
+
+        match (
+            contiguous,
+            distance_to_tail <= distance_to_head,
+            idx >= self.tail(),
+        ) {
+            (true, true, _) => {
+                unsafe {
+                    // contiguous, remove closer to tail:
+                    //
+                    //             T   R         H
+                    //      [. . . o o x o o o o . . . . . .]
+                    //
+                    //               T           H
+                    //      [. . . . o o o o o o . . . . . .]
+                    //               M M
+
+                    let tail = self.tail();
+                    self.as_uninit_slice_mut()[tail..=idx].rotate_right(1);
+                    self.set_tail_forward();
+                }
+            }
+            (true, false, _) => {
+                unsafe {
+                    // contiguous, remove closer to head:
+                    //
+                    //             T       R     H
+                    //      [. . . o o o o x o o . . . . . .]
+                    //
+                    //             T           H
+                    //      [. . . o o o o o o . . . . . . .]
+                    //                     M M
+
+                    let head = self.head();
+                    self.as_uninit_slice_mut()[idx..head].rotate_left(1);
+                    self.set_head_backward();
+                }
+            }
+            (false, true, true) => {
+                unsafe {
+                    // discontiguous, remove closer to tail, tail section:
+                    //
+                    //                   H         T   R
+                    //      [o o o o o o . . . . . o o x o o]
+                    //
+                    //                   H           T
+                    //      [o o o o o o . . . . . . o o o o]
+                    //                               M M
+
+                    let tail = self.tail();
+                    self.copy(tail + 1, tail, index);
+                    self.set_tail_forward();
+                }
+            }
+            (false, false, false) => {
+                unsafe {
+                    // discontiguous, remove closer to head, head section:
+                    //
+                    //               R     H           T
+                    //      [o o o o x o o . . . . . . o o o]
+                    //
+                    //                   H             T
+                    //      [o o o o o o . . . . . . . o o o]
+                    //               M M
+
+                    let head = self.head();
+                    self.copy(idx, idx + 1, head - idx - 1);
+                    self.set_head_backward();
+                }
+            }
+            (false, false, true) => {
+                unsafe {
+                    // discontiguous, remove closer to head, tail section:
+                    //
+                    //             H           T         R
+                    //      [o o o . . . . . . o o o o o x o]
+                    //
+                    //           H             T
+                    //      [o o . . . . . . . o o o o o o o]
+                    //       M M                         M M
+                    //
+                    // or quasi-discontiguous, remove next to head, tail section:
+                    //
+                    //       H                 T         R
+                    //      [. . . . . . . . . o o o o o x o]
+                    //
+                    //                         T           H
+                    //      [. . . . . . . . . o o o o o o .]
+                    //                                   M
+
+                    // draw in elements in the tail section
+                    self.copy(idx, idx + 1, CAP - idx - 1);
+
+                    // Prevents underflow.
+                    if self.head() != 0 {
+                        // copy first element into empty spot
+                        self.copy(CAP - 1, 0, 1);
+
+                        // move elements in the head section backwards
+                        let head = self.head();
+                        self.copy(0, 1, head - 1);
+                    }
+
+                    self.set_head_backward();
+                }
+            }
+            (false, true, false) => {
+                unsafe {
+                    // discontiguous, remove closer to tail, head section:
+                    //
+                    //           R               H     T
+                    //      [o o x o o o o o o o . . . o o o]
+                    //
+                    //                           H       T
+                    //      [o o o o o o o o o o . . . . o o]
+                    //       M M M                       M M
+
+                    let tail = self.tail();
+                    // draw in elements up to idx
+                    self.copy(1, 0, idx);
+
+                    // copy last element into empty spot
+                    self.copy(0, CAP - 1, 1);
+
+                    // move elements from tail to end forward, excluding the last one
+                    self.copy(tail + 1, tail, CAP - tail - 1);
+
+                    self.set_tail_forward();
+                }
+            }
+        }
+
+        elem
+    }
+
+    /// Removes the first element equal to `value` and returns it, or `None`
+    /// if there is no such element.
+    ///
+    /// The order of the remaining elements is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 1].into();
+    ///
+    /// assert_eq!(buf.remove_item(&1), Some(1));
+    /// assert_eq!(buf, [0, 2, 1].into());
+    /// assert_eq!(buf.remove_item(&3), None);
+    /// ```
+    pub fn remove_item(&mut self, value: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let index = self.position(|x| x == value)?;
+        self.remove(index)
+    }
+
+    /// Splits the collection into two at the given index.
+    ///
+    /// Returns a newly allocated `Self`. `self` contains elements `[0, at)`,
+    /// and the returned `Self` contains elements `[at, len)`.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    ///
+    /// // buf = [0], buf2 = [1, 2]
+    /// let buf2 = buf.split_off(1);
+    ///
+    /// assert_eq!(buf.len(), 1);
+    /// assert_eq!(buf2.len(), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "`at` out of bounds");
+
+        let other_len = len - at;
+        let mut other = Self::new();
+
+        unsafe {
+            let (first_half, second_half) = self.as_slices();
+
+            let first_len = first_half.len();
+            let second_len = second_half.len();
+            if at < first_len {
+                // `at` lies in the first half.
+                let amount_in_first = first_len - at;
+
+                ptr::copy_nonoverlapping(
+                    first_half.as_ptr().add(at),
+                    other.ptr_mut(),
+                    amount_in_first,
+                );
+
+                // just take all of the second half.
+                ptr::copy_nonoverlapping(
+                    second_half.as_ptr(),
+                    other.ptr_mut().add(amount_in_first),
+                    second_len,
+                );
+            } else {
+                // `at` lies in the second half, need to factor in the elements we skipped
+                // in the first half.
+                let offset = at - first_len;
+                let amount_in_second = second_len - offset;
+                ptr::copy_nonoverlapping(
+                    second_half.as_ptr().add(offset),
+                    other.ptr_mut(),
+                    amount_in_second,
+                );
+            }
+        }
+
+        // Cleanup where the ends of the buffers are
+        unsafe {
+            self.set_len(at);
+            other.set_len(other_len);
+        }
+
+        other
+    }
+
+    /// Removes the first `n` elements and returns them as a new `Self`, in
+    /// the same order.
+    ///
+    /// `n` is clamped to the length of the deque. `self` keeps the remaining
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+    ///
+    /// let front = buf.take_front(3);
+    ///
+    /// assert_eq!(front, [0, 1, 2].into());
+    /// assert_eq!(buf, [3].into());
+    /// ```
+    pub fn take_front(&mut self, n: usize) -> Self {
+        let len = self.len();
+        let n = cmp::min(n, len);
+        let mut other = Self::new();
+
+        unsafe {
+            let (first_half, second_half) = self.as_slices();
+
+            let amount_in_first = cmp::min(first_half.len(), n);
+            ptr::copy_nonoverlapping(first_half.as_ptr(), other.ptr_mut(), amount_in_first);
+            ptr::copy_nonoverlapping(
+                second_half.as_ptr(),
+                other.ptr_mut().add(amount_in_first),
+                n - amount_in_first,
+            );
+        }
+
+        unsafe {
+            let new_tail = Self::wrap_add(self.tail(), n);
+            self.set_tail(new_tail);
+            self.set_len(len - n);
+            other.set_len(n);
+        }
+
+        other
+    }
+
+    /// Removes the last `n` elements and returns them as a new `Self`, in
+    /// the same order.
+    ///
+    /// `n` is clamped to the length of the deque, which makes this the same
+    /// as `split_off(len() - n)` without the risk of underflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+    ///
+    /// let back = buf.split_off_back(3);
+    ///
+    /// assert_eq!(back, [1, 2, 3].into());
+    /// assert_eq!(buf, [0].into());
+    ///
+    /// assert_eq!(buf.split_off_back(5), [0].into());
+    /// assert!(buf.is_empty());
+    /// ```
+    pub fn split_off_back(&mut self, n: usize) -> Self {
+        let len = self.len();
+        self.split_off(len - cmp::min(n, len))
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&e)` returns false.
+    /// This method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// Returns the number of elements removed.
+    ///
+    /// Each rejected element is dropped as soon as `f` rejects it. If `f` or
+    /// the destructor of a rejected element panics, the elements rejected so
+    /// far are removed and all the others are kept, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back(0..4);
+    /// let removed = buf.retain(|&x| x % 2 == 0);
+    ///
+    /// assert_eq!(removed, 2);
+    /// assert_eq!(buf, [0, 2].into());
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // Rejected elements are dropped right away, and kept elements are
+        // moved back over the `del` slots they leave behind, which sit just
+        // before the next element to check.
+        struct Compaction<'a, T, const CAP: usize, B: Behavior> {
+            deque: &'a mut ArrayDeque<T, CAP, B>,
+            len: usize,
+            processed: usize,
+            del: usize,
+        }
+
+        impl<'a, T, const CAP: usize, B: Behavior> Drop for Compaction<'a, T, CAP, B> {
+            fn drop(&mut self) {
+                // Also runs if `f` or a destructor panics, moving the unchecked
+                // elements back to close the gap
+                unsafe {
+                    if self.del > 0 {
+                        let tail = self.deque.tail();
+                        self.deque.wrap_copy(
+                            ArrayDeque::<T, CAP, B>::wrap_add(tail, self.processed - self.del),
+                            ArrayDeque::<T, CAP, B>::wrap_add(tail, self.processed),
+                            self.len - self.processed,
+                        );
+                    }
+                    self.deque.set_len(self.len - self.del);
+                }
+            }
+        }
+
+        let len = self.len();
+        let tail = self.tail();
+        let mut guard = Compaction {
+            deque: self,
+            len,
+            processed: 0,
+            del: 0,
+        };
+        while guard.processed < len {
+            let xs = guard.deque.ptr_mut();
+            unsafe {
+                let cur = xs.add(Self::wrap_add(tail, guard.processed));
+                if !f(&*cur) {
+                    // Count the slot as a hole first, in case the drop panics
+                    guard.processed += 1;
+                    guard.del += 1;
+                    ptr::drop_in_place(cur);
+                } else {
+                    if guard.del > 0 {
+                        let hole = xs.add(Self::wrap_add(tail, guard.processed - guard.del));
+                        ptr::copy_nonoverlapping(cur, hole, 1);
+                    }
+                    guard.processed += 1;
+                }
+            }
+        }
+        guard.del
+    }
+
+    /// Retains only the elements specified by the predicate, which is also
+    /// given the index of each element.
+    ///
+    /// Same as [`retain`](#method.retain), except that `f` is called with
+    /// the original index of the element, counted from the front before any
+    /// element is removed.
+    ///
+    /// Returns the number of elements removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [5, 6, 7, 8, 9].into();
+    /// let removed = buf.retain_indexed(|i, &x| i % 2 == 0 || x == 8);
+    ///
+    /// assert_eq!(removed, 1);
+    /// assert_eq!(buf, [5, 7, 8, 9].into());
+    /// ```
+    pub fn retain_indexed<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(usize, &T) -> bool,
+    {
+        let mut index = 0;
+        self.retain(|x| {
+            let keep = f(index, x);
+            index += 1;
+            keep
+        })
+    }
+
+    /// Removes every element equal to `value`, preserving the order of the
+    /// remaining elements.
+    ///
+    /// Returns the number of elements removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [1, 0, 1, 2, 1].into();
+    ///
+    /// assert_eq!(buf.remove_all_eq(&1), 3);
+    /// assert_eq!(buf, [0, 2].into());
+    /// ```
+    pub fn remove_all_eq(&mut self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.retain(|x| x != value)
+    }
+
+    /// Replaces every element equal to `old` with a clone of `new`.
+    ///
+    /// Returns the number of elements replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [1, 0, 1, 2].into();
+    ///
+    /// assert_eq!(buf.replace_all(&1, &3), 2);
+    /// assert_eq!(buf, [3, 0, 3, 2].into());
+    /// ```
+    pub fn replace_all(&mut self, old: &T, new: &T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut count = 0;
+        let (a, b) = self.as_mut_slices();
+        for x in a.iter_mut().chain(b) {
+            if *x == *old {
+                *x = new.clone();
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Replaces the element at `index` with the value computed by `f` from
+    /// the old element.
+    ///
+    /// If `f` panics, the element at `index` is removed from the deque before
+    /// the panic propagates.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 2> = ArrayDeque::new();
+    ///
+    /// buf.push_back(String::from("foo"));
+    /// buf.push_back(String::from("bar"));
+    ///
+    /// buf.replace_with(1, |s| s + "baz");
+    ///
+    /// assert_eq!(buf[1], "barbaz");
+    /// ```
+    #[track_caller]
+    pub fn replace_with<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(T) -> T,
+    {
+        struct Hole<'a, T, const CAP: usize, B: Behavior> {
+            deque: &'a mut ArrayDeque<T, CAP, B>,
+            index: usize,
+        }
+
+        impl<'a, T, const CAP: usize, B: Behavior> Drop for Hole<'a, T, CAP, B> {
+            fn drop(&mut self) {
+                // The element has been moved into the panicking closure, so
+                // close the gap by shifting the shorter side over the slot,
+                // without reading it again.
+                let deque = &mut *self.deque;
+                let index = self.index;
+                let len = deque.len();
+                let tail = deque.tail();
+                unsafe {
+                    if index < len - index - 1 {
+                        let new_tail = ArrayDeque::<T, CAP, B>::wrap_add(tail, 1);
+                        deque.wrap_copy(new_tail, tail, index);
+                        deque.set_tail_forward();
+                    } else {
+                        let idx = ArrayDeque::<T, CAP, B>::wrap_add(tail, index);
+                        let next = ArrayDeque::<T, CAP, B>::wrap_add(idx, 1);
+                        deque.wrap_copy(idx, next, len - index - 1);
+                        deque.set_head_backward();
+                    }
+                }
+            }
+        }
+
+        let len = self.len();
+        assert!(
+            index < len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            index
+        );
+
+        let idx = Self::wrap_add(self.tail(), index);
+        unsafe {
+            let old = self.buffer_read(idx);
+            let hole = Hole { deque: self, index };
+            let new = f(old);
+            mem::forget(hole);
+            self.buffer_write(idx, new);
+        }
+    }
+
+    /// Merges the sorted contents of `other` into `self`, which is also
+    /// expected to be sorted.
+    ///
+    /// Elements are moved out of `other`, smallest first, until either `other`
+    /// is empty or `self` is full. Elements that don't fit are left in `other`.
+    /// The merge is stable: elements of `self` are placed before equal
+    /// elements of `other`.
+    ///
+    /// If either deque is not sorted, the resulting order is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 6> = [1, 4, 6].into();
+    /// let mut other: ArrayDeque<_, 6> = [2, 3, 5, 7].into();
+    ///
+    /// buf.merge_sorted(&mut other);
+    ///
+    /// assert_eq!(buf, [1, 2, 3, 4, 5, 6].into());
+    /// assert_eq!(other, [7].into());
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(self.len() + other.len())` time and no extra space.
+    pub fn merge_sorted(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        // Merges from the back into the free slots of `self`, so every element
+        // is moved at most once.
+        //
+        // Layout of `self` while merging, in logical indices:
         //
-        // Key: H - self.head
-        //      T - self.tail
-        //      o - Valid element
-        //      x - Element marked for removal
-        //      R - Indicates element that is being removed
-        //      M - Indicates element was moved
+        //      [0 .. dst_left)                     unmerged elements
+        //      [dst_left .. dst_left + src_left)   free slots
+        //      [dst_left + src_left .. )           merged elements
+        //
+        // and of `other`:
+        //
+        //      [0 .. src_left)                     unmerged elements
+        //      [src_left .. taken)                 elements moved into `self`
+        //      [taken .. )                         elements left in `other`
+        struct Merge<'a, T, const CAP: usize, B: Behavior> {
+            dst: &'a mut ArrayDeque<T, CAP, B>,
+            src: &'a mut ArrayDeque<T, CAP, B>,
+            dst_left: usize,
+            src_left: usize,
+            merged: usize,
+            taken: usize,
+        }
+
+        impl<'a, T, const CAP: usize, B: Behavior> Drop for Merge<'a, T, CAP, B> {
+            fn drop(&mut self) {
+                // On a panicking comparison, close the gaps so that both deques
+                // own every element exactly once. When the merge completes,
+                // `src_left` is zero and both copies are no-ops.
+                unsafe {
+                    let dst_tail = self.dst.tail();
+                    self.dst.wrap_copy(
+                        ArrayDeque::<T, CAP, B>::wrap_add(dst_tail, self.dst_left),
+                        ArrayDeque::<T, CAP, B>::wrap_add(dst_tail, self.dst_left + self.src_left),
+                        self.merged,
+                    );
+                    self.dst.set_len(self.dst_left + self.merged);
+
+                    let moved = self.taken - self.src_left;
+                    let src_tail = self.src.tail();
+                    let new_src_tail = ArrayDeque::<T, CAP, B>::wrap_add(src_tail, moved);
+                    self.src.wrap_copy(new_src_tail, src_tail, self.src_left);
+                    self.src.set_tail(new_src_tail);
+                    self.src.set_len(self.src.len() - moved);
+                }
+            }
+        }
+
+        let len = self.len();
+        let taken = cmp::min(other.len(), CAP - len);
+        if taken == 0 {
+            return;
+        }
+
+        let mut merge = Merge {
+            dst: self,
+            src: other,
+            dst_left: len,
+            src_left: taken,
+            merged: 0,
+            taken,
+        };
+
+        while merge.src_left > 0 {
+            let from_dst =
+                merge.dst_left > 0 && merge.dst[merge.dst_left - 1] > merge.src[merge.src_left - 1];
+
+            let dst_tail = merge.dst.tail();
+            let slot = Self::wrap_add(dst_tail, merge.dst_left + merge.src_left - 1);
+            unsafe {
+                let dst = merge.dst.ptr_mut().add(slot);
+                if from_dst {
+                    let idx = Self::wrap_add(dst_tail, merge.dst_left - 1);
+                    ptr::copy_nonoverlapping(merge.dst.ptr().add(idx), dst, 1);
+                    merge.dst_left -= 1;
+                } else {
+                    let idx = Self::wrap_add(merge.src.tail(), merge.src_left - 1);
+                    ptr::copy_nonoverlapping(merge.src.ptr().add(idx), dst, 1);
+                    merge.src_left -= 1;
+                }
+            }
+            merge.merged += 1;
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// `ArrayDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 7> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
+    ///
+    /// buf.push_front(2);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[2][..], &[0, 1][..]));
+    /// ```
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let contiguous = self.is_contiguous();
+        let head = self.head();
+        let tail = self.tail();
+        let buf = self.as_uninit_slice();
+
+        if contiguous {
+            let (empty, buf) = buf.split_at(0);
+            unsafe {
+                (
+                    slice_assume_init_ref(&buf[tail..head]),
+                    slice_assume_init_ref(empty),
+                )
+            }
+        } else {
+            let (mid, right) = buf.split_at(tail);
+            let (left, _) = mid.split_at(head);
+
+            unsafe { (slice_assume_init_ref(right), slice_assume_init_ref(left)) }
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the elements from
+    /// index `start` to the back of the `ArrayDeque`.
+    ///
+    /// Like [`as_slices`](#method.as_slices), the first slice is only empty if
+    /// both are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 7> = [1, 2, 3].into();
+    /// buf.push_front(0).unwrap();
+    ///
+    /// assert_eq!(buf.as_slices(), (&[0][..], &[1, 2, 3][..]));
+    /// assert_eq!(buf.as_slices_from(2), (&[2, 3][..], &[][..]));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn as_slices_from(&self, start: usize) -> (&[T], &[T]) {
+        assert!(start <= self.len(), "`start` out of bounds");
+        let (a, b) = self.as_slices();
+        if start < a.len() {
+            (&a[start..], b)
+        } else {
+            (&b[start - a.len()..], &[])
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the
+    /// `ArrayDeque`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 7> = ArrayDeque::new();
+    ///
+    /// buf.push_back(0);
+    /// buf.push_back(1);
+    ///
+    /// assert_eq!(buf.as_mut_slices(), (&mut [0, 1][..], &mut[][..]));
+    ///
+    /// buf.push_front(2);
+    ///
+    /// assert_eq!(buf.as_mut_slices(), (&mut[2][..], &mut[0, 1][..]));
+    /// ```
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let contiguous = self.is_contiguous();
+        let head = self.head();
+        let tail = self.tail();
+        let buf = self.as_uninit_slice_mut();
+
+        if contiguous {
+            let (empty, buf) = buf.split_at_mut(0);
+            unsafe {
+                (
+                    slice_assume_init_mut(&mut buf[tail..head]),
+                    slice_assume_init_mut(empty),
+                )
+            }
+        } else {
+            let (mid, right) = buf.split_at_mut(tail);
+            let (left, _) = mid.split_at_mut(head);
+
+            unsafe { (slice_assume_init_mut(right), slice_assume_init_mut(left)) }
+        }
+    }
+
+    /// Returns the contents of the `ArrayDeque` as a single slice, or `None`
+    /// if the elements wrap around the end of the buffer.
+    ///
+    /// Call [`linearize`](#method.linearize) first to make sure this
+    /// succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    /// assert_eq!(buf.try_as_slice(), Some(&[1, 2][..]));
+    ///
+    /// buf.push_front(0);
+    /// assert_eq!(buf.try_as_slice(), None);
+    ///
+    /// buf.linearize();
+    /// assert_eq!(buf.try_as_slice(), Some(&[0, 1, 2][..]));
+    /// ```
+    pub fn try_as_slice(&self) -> Option<&[T]> {
+        match self.as_slices() {
+            (front, []) => Some(front),
+            _ => None,
+        }
+    }
+
+    /// Linearizes the buffer and splits it into arrays of `M` elements,
+    /// starting at the front, and a remainder shorter than `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 8> = ArrayDeque::new();
+    /// buf.extend_back([3, 4, 5, 6, 7]);
+    /// buf.extend_front([2, 1, 0]);
+    ///
+    /// let (chunks, remainder) = buf.as_chunks::<3>();
+    ///
+    /// assert_eq!(chunks, &[[0, 1, 2], [3, 4, 5]]);
+    /// assert_eq!(remainder, &[6, 7]);
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(len())` time and no extra space.
+    #[track_caller]
+    pub fn as_chunks<const M: usize>(&mut self) -> (&[[T; M]], &[T]) {
+        assert!(M != 0, "chunk size must be non-zero");
+        self.linearize();
+        let slice = self.as_slices().0;
+        let count = slice.len() / M;
+        let (chunks, remainder) = slice.split_at(count * M);
+        // SAFETY: `chunks` holds exactly `count * M` elements, and `[T; M]` has
+        // the same layout as `M` consecutive `T`s
+        let chunks = unsafe { std::slice::from_raw_parts(chunks.as_ptr().cast(), count) };
+        (chunks, remainder)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the deque at a time,
+    /// starting at the front.
+    ///
+    /// Each chunk is a pair of slices, the second of which is only non-empty
+    /// when the chunk wraps around the end of the buffer. The last chunk is
+    /// shorter if `chunk_size` does not divide the length of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [1, 1, 1, 1, 1].into();
+    ///
+    /// for (i, (a, b)) in buf.chunks_mut(2).enumerate() {
+    ///     a.iter_mut().chain(b).for_each(|x| *x += i);
+    /// }
+    ///
+    /// assert_eq!(buf, [1, 1, 2, 2, 3].into());
+    /// ```
+    #[track_caller]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let (front, back) = self.as_mut_slices();
+        ChunksMut {
+            front,
+            back,
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the deque at a time,
+    /// starting at the back.
+    ///
+    /// Each chunk is a pair of slices, the second of which is only non-empty
+    /// when the chunk wraps around the end of the buffer. The last chunk is
+    /// shorter if `chunk_size` does not divide the length of the deque.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 5> = [1, 1, 1, 1, 1].into();
+    ///
+    /// for (i, (a, b)) in buf.rchunks_mut(2).enumerate() {
+    ///     a.iter_mut().chain(b).for_each(|x| *x += i);
+    /// }
+    ///
+    /// assert_eq!(buf, [3, 2, 2, 1, 1].into());
+    /// ```
+    #[track_caller]
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> RChunksMut<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let (front, back) = self.as_mut_slices();
+        RChunksMut {
+            front,
+            back,
+            chunk_size,
+        }
+    }
+
+    /// Returns an iterator over the runs of consecutive elements for which
+    /// `same` returns `true` for every adjacent pair, starting at the front.
+    ///
+    /// Each run is a pair of slices, the second of which is only non-empty
+    /// when the run wraps around the end of the buffer. `same` is called with
+    /// the earlier element first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 6> = [1, 1, 2, 3, 3, 3].into();
+    ///
+    /// let lens: Vec<_> = buf.chunk_by(|a, b| a == b).map(|(a, b)| a.len() + b.len()).collect();
+    /// assert_eq!(lens, [2, 1, 3]);
+    /// ```
+    pub fn chunk_by<F>(&self, same: F) -> ChunkBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let (front, back) = self.as_slices();
+        ChunkBy { front, back, same }
+    }
+
+    /// Returns an iterator over the runs of elements separated by elements
+    /// for which `pred` returns `true`, starting at the front.
+    ///
+    /// As with `slice::split`, the matching elements are not included, and
+    /// a run is empty if two matching elements are adjacent, or at either
+    /// end of the deque. Each run is a pair of slices, the second of which is
+    /// only non-empty when the run wraps around the end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 8> = (*b"ab\ncd\n").into();
+    ///
+    /// let lines: Vec<_> = buf.split_by(|&b| b == b'\n').map(|(a, b)| [a, b].concat()).collect();
+    /// assert_eq!(lines, [&b"ab"[..], b"cd", b""]);
+    /// ```
+    pub fn split_by<P>(&self, pred: P) -> SplitBy<'_, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (front, back) = self.as_slices();
+        SplitBy {
+            front,
+            back,
+            pred,
+            finished: false,
+        }
+    }
+
+    /// Returns the first `n` elements of the `ArrayDeque` as a pair of slices
+    /// which contain them in order.
+    ///
+    /// `n` is clamped to the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back([1, 2]);
+    /// buf.extend_front([0]);
+    ///
+    /// assert_eq!(buf.front_n(2), (&[0][..], &[1][..]));
+    /// assert_eq!(buf.front_n(5), (&[0][..], &[1, 2][..]));
+    /// ```
+    pub fn front_n(&self, n: usize) -> (&[T], &[T]) {
+        let n = cmp::min(n, self.len());
+        let (a, b) = self.as_slices();
+        if n <= a.len() {
+            (&a[..n], &b[..0])
+        } else {
+            (a, &b[..n - a.len()])
+        }
+    }
+
+    /// Returns the last `n` elements of the `ArrayDeque` as a pair of slices
+    /// which contain them in order.
+    ///
+    /// `n` is clamped to the length of the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back([1, 2]);
+    /// buf.extend_front([0]);
+    ///
+    /// assert_eq!(buf.back_n(2), (&[1, 2][..], &[][..]));
+    /// assert_eq!(buf.back_n(5), (&[0][..], &[1, 2][..]));
+    /// ```
+    pub fn back_n(&self, n: usize) -> (&[T], &[T]) {
+        let n = cmp::min(n, self.len());
+        let (a, b) = self.as_slices();
+        if n <= b.len() {
+            (&b[b.len() - n..], &b[..0])
+        } else {
+            (&a[a.len() - (n - b.len())..], b)
+        }
+    }
+
+    /// Returns references to the first `M` elements of the `ArrayDeque` as an
+    /// array, or `None` if it has fewer than `M` elements.
+    ///
+    /// An array of references is returned as the elements may wrap around the
+    /// end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [2, 3].into();
+    /// buf.push_front(1).unwrap();
+    ///
+    /// let [a, b] = buf.first_chunk::<2>().unwrap();
+    /// assert_eq!((*a, *b), (1, 2));
+    /// assert_eq!(buf.first_chunk::<4>(), None);
+    /// ```
+    pub fn first_chunk<const M: usize>(&self) -> Option<[&T; M]> {
+        if self.len() < M {
+            return None;
+        }
+        Some(array_of_refs(self.iter()))
+    }
+
+    /// Returns references to the last `M` elements of the `ArrayDeque` as an
+    /// array, or `None` if it has fewer than `M` elements.
+    ///
+    /// An array of references is returned as the elements may wrap around the
+    /// end of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = [2, 3].into();
+    /// buf.push_front(1).unwrap();
+    ///
+    /// let [a, b] = buf.last_chunk::<2>().unwrap();
+    /// assert_eq!((*a, *b), (2, 3));
+    /// assert_eq!(buf.last_chunk::<4>(), None);
+    /// ```
+    pub fn last_chunk<const M: usize>(&self) -> Option<[&T; M]> {
+        if self.len() < M {
+            return None;
+        }
+        let mut iter = self.iter();
+        iter.advance(self.len() - M);
+        Some(array_of_refs(iter))
+    }
+
+    /// Returns the elements in the specified range as a pair of slices which
+    /// contain them in order, or `None` if the range is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back([1, 2, 3]);
+    /// buf.extend_front([0]);
+    ///
+    /// assert_eq!(buf.get_range(..2), Some((&[0][..], &[1][..])));
+    /// assert_eq!(buf.get_range(2..), Some((&[2, 3][..], &[][..])));
+    /// assert_eq!(buf.get_range(2..5), None);
+    /// ```
+    pub fn get_range<R>(&self, range: R) -> Option<(&[T], &[T])>
+    where
+        R: RangeArgument<usize>,
+    {
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(self.len());
+        if start > end || end > self.len() {
+            return None;
+        }
+
+        let (a, b) = self.as_slices();
+        let mid = a.len();
+        if end <= mid {
+            Some((&a[start..end], &[]))
+        } else if start >= mid {
+            Some((&b[start - mid..end - mid], &[]))
+        } else {
+            Some((&a[start..], &b[..end - mid]))
+        }
+    }
+
+    /// Returns the elements in the specified range as a pair of mutable slices
+    /// which contain them in order, or `None` if the range is out of bounds.
+    ///
+    /// Element at index 0 is the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back([1, 2, 3]);
+    /// buf.extend_front([0]);
+    ///
+    /// if let Some((a, b)) = buf.get_range_mut(1..3) {
+    ///     a.iter_mut().chain(b).for_each(|x| *x = 0);
+    /// }
+    ///
+    /// assert_eq!(buf, [0, 0, 0, 3].into());
+    /// assert_eq!(buf.get_range_mut(2..5), None);
+    /// ```
+    pub fn get_range_mut<R>(&mut self, range: R) -> Option<(&mut [T], &mut [T])>
+    where
+        R: RangeArgument<usize>,
+    {
+        let start = range.start().unwrap_or(0);
+        let end = range.end().unwrap_or(self.len());
+        if start > end || end > self.len() {
+            return None;
+        }
+
+        let (a, b) = self.as_mut_slices();
+        let mid = a.len();
+        if end <= mid {
+            Some((&mut a[start..end], &mut []))
+        } else if start >= mid {
+            Some((&mut b[start - mid..end - mid], &mut []))
+        } else {
+            Some((&mut a[start..], &mut b[..end - mid]))
+        }
+    }
+
+    /// Divides the `ArrayDeque` into two disjoint mutable views at an index.
+    ///
+    /// The first view contains elements `[0, mid)` and the second contains
+    /// elements `[mid, len)`, each as a pair of slices which contain them in
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
+    ///
+    /// buf.extend_back([1, 2]);
+    /// buf.extend_front([0]);
+    ///
+    /// let ((a, b), (c, d)) = buf.split_at_mut(2);
+    /// assert_eq!((&*a, &*b), (&[0][..], &[1][..]));
+    /// assert_eq!((&*c, &*d), (&[2][..], &[][..]));
+    ///
+    /// a[0] = 3;
+    /// c[0] = 4;
+    /// assert_eq!(buf, [3, 1, 4].into());
+    /// ```
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn split_at_mut(&mut self, mid: usize) -> ((&mut [T], &mut [T]), (&mut [T], &mut [T])) {
+        assert!(mid <= self.len(), "`mid` out of bounds");
+
+        let (a, b) = self.as_mut_slices();
+        if mid <= a.len() {
+            let (front, back) = a.split_at_mut(mid);
+            ((front, &mut []), (back, b))
+        } else {
+            let mid = mid - a.len();
+            let (front, back) = b.split_at_mut(mid);
+            ((a, front), (back, &mut []))
+        }
+    }
+
+    /// Replaces the contents of `target` with clones of the elements of
+    /// `self`, reusing the storage of `target`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<_, 3> = [1, 2, 3].into();
+    /// let mut target: ArrayDeque<_, 3> = [4, 5].into();
+    ///
+    /// buf.clone_into(&mut target);
+    ///
+    /// assert_eq!(target, buf);
+    /// ```
+    pub fn clone_into(&self, target: &mut Self)
+    where
+        T: Clone,
+    {
+        target.clear();
+        for element in self {
+            unsafe {
+                target.push_back_unchecked(element.clone());
+            }
+        }
+    }
+
+    /// Swaps the elements of the deque with those of `other`.
+    ///
+    /// This exchanges the whole state of both deques, including their
+    /// layouts, and never clones or drops any element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = [1, 2, 3].into();
+    /// let mut other: ArrayDeque<_, 3> = [4].into();
+    ///
+    /// buf.swap_contents(&mut other);
+    ///
+    /// assert_eq!(buf, [4].into());
+    /// assert_eq!(other, [1, 2, 3].into());
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(capacity())` time, as the backing arrays are swapped.
+    pub fn swap_contents(&mut self, other: &mut Self) {
+        mem::swap(self, other);
+    }
+
+    /// Replaces the contents of the deque with a copy of the first
+    /// `capacity()` elements of `src`, starting at the beginning of the
+    /// underlying buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = [1, 2].into();
+    ///
+    /// buf.replace_contents(&[3, 4, 5, 6]);
+    ///
+    /// assert_eq!(buf.as_slices(), (&[3, 4, 5][..], &[][..]));
+    /// ```
+    pub fn replace_contents(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        let len = cmp::min(src.len(), CAP);
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), self.ptr_mut(), len);
+            self.set_tail(0);
+            self.set_len(len);
+        }
+    }
+
+    /// Copies the contents of the `ArrayDeque` into a new `Vec`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
+    ///
+    /// buf.push_back(1);
+    /// buf.push_back(2);
+    /// buf.push_front(0);
+    ///
+    /// assert_eq!(buf.to_vec(), vec![0, 1, 2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let (a, b) = self.as_slices();
+        let mut vec = Vec::with_capacity(self.len());
+        vec.extend_from_slice(a);
+        vec.extend_from_slice(b);
+        vec
+    }
+}
+
+impl<const CAP: usize, B: Behavior> ArrayDeque<u8, CAP, B> {
+    /// Linearize the buffer and return its bytes as a single slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.extend_back(*b"ab");
+    /// buf.extend_front(*b"dc");
+    ///
+    /// assert_eq!(buf.as_bytes(), b"cdab");
+    /// ```
+    ///
+    /// # Complexity
+    ///
+    /// Takes `O(len())` time and no extra space.
+    pub fn as_bytes(&mut self) -> &[u8] {
+        self.linearize();
+        self.as_slices().0
+    }
+
+    /// Create a deque holding a copy of `bytes`.
+    ///
+    /// Return `Err(CapacityError)` if `bytes` does not fit into the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let buf: ArrayDeque<u8, 4> = ArrayDeque::from_bytes(b"abc").unwrap();
+    /// assert_eq!(buf, (*b"abc").into());
+    ///
+    /// assert!(ArrayDeque::<u8, 2>::from_bytes(b"abc").is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CapacityError> {
+        if bytes.len() > CAP {
+            return Err(CapacityError { element: () });
+        }
+        Ok(Self::from_slice_truncate(bytes))
+    }
+
+    /// Append all of `src` to the back of the deque.
+    ///
+    /// Return `Err(CapacityError)` without modifying the deque if there is
+    /// not enough free space for all of `src`, regardless of the behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    ///
+    /// assert!(buf.put_slice(b"abc").is_ok());
+    /// assert!(buf.put_slice(b"de").is_err());
+    /// assert_eq!(buf, (*b"abc").into());
+    /// ```
+    pub fn put_slice(&mut self, src: &[u8]) -> Result<(), CapacityError> {
+        if src.len() > CAP - self.len() {
+            return Err(CapacityError { element: () });
+        }
+        self.fill_back(src.iter().copied());
+        Ok(())
+    }
+
+    /// Append as many bytes from the start of `src` as fit to the back of the
+    /// deque, and return how many were appended.
+    ///
+    /// The bytes are copied in bulk into both free regions returned by
+    /// [`uninit_slices`](#method.uninit_slices). Nothing is evicted,
+    /// regardless of the behavior. Unlike [`read_from`](#method.read_from),
+    /// this doesn't need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    ///
+    /// assert_eq!(buf.read_packet(b"abc"), 3);
+    /// assert_eq!(buf.read_packet(b"def"), 1);
+    /// assert_eq!(buf, (*b"abcd").into());
+    /// ```
+    pub fn read_packet(&mut self, src: &[u8]) -> usize {
+        let count = cmp::min(src.len(), CAP - self.len());
+        unsafe {
+            self.copy_to_back(&src[..count]);
+        }
+        count
+    }
+
+    /// Append a byte to the back of the deque.
+    ///
+    /// Return `Err(CapacityError)` if the deque is full, regardless of the
+    /// behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 1> = ArrayDeque::new();
+    ///
+    /// assert!(buf.put_u8(1).is_ok());
+    /// assert!(buf.put_u8(2).is_err());
+    /// ```
+    pub fn put_u8(&mut self, value: u8) -> Result<(), CapacityError> {
+        self.put_slice(&[value])
+    }
+
+    /// Append a `u16` to the back of the deque in big-endian byte order.
+    ///
+    /// Return `Err(CapacityError)` without modifying the deque if there is
+    /// not enough free space, regardless of the behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.put_u16_be(0x0102).unwrap();
+    ///
+    /// assert_eq!(buf, [1, 2].into());
+    /// ```
+    pub fn put_u16_be(&mut self, value: u16) -> Result<(), CapacityError> {
+        self.put_slice(&value.to_be_bytes())
+    }
+
+    /// Append a `u16` to the back of the deque in little-endian byte order.
+    ///
+    /// Return `Err(CapacityError)` without modifying the deque if there is
+    /// not enough free space, regardless of the behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.put_u16_le(0x0102).unwrap();
+    ///
+    /// assert_eq!(buf, [2, 1].into());
+    /// ```
+    pub fn put_u16_le(&mut self, value: u16) -> Result<(), CapacityError> {
+        self.put_slice(&value.to_le_bytes())
+    }
+
+    /// Remove a byte from the front of the deque, or return `None` if it is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.put_u8(1).unwrap();
+    ///
+    /// assert_eq!(buf.get_u8(), Some(1));
+    /// assert_eq!(buf.get_u8(), None);
+    /// ```
+    pub fn get_u8(&mut self) -> Option<u8> {
+        self.pop_front()
+    }
+
+    /// Remove a big-endian `u16` from the front of the deque.
+    ///
+    /// Return `None` without modifying the deque if it holds fewer than two
+    /// bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.put_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(buf.get_u16_be(), Some(0x0102));
+    /// assert_eq!(buf.get_u16_be(), None);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    pub fn get_u16_be(&mut self) -> Option<u16> {
+        self.get_bytes().map(u16::from_be_bytes)
+    }
+
+    /// Remove a little-endian `u16` from the front of the deque.
+    ///
+    /// Return `None` without modifying the deque if it holds fewer than two
+    /// bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.put_slice(&[1, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(buf.get_u16_le(), Some(0x0201));
+    /// assert_eq!(buf.get_u16_le(), None);
+    /// assert_eq!(buf.len(), 1);
+    /// ```
+    pub fn get_u16_le(&mut self) -> Option<u16> {
+        self.get_bytes().map(u16::from_le_bytes)
+    }
+
+    fn get_bytes<const N: usize>(&mut self) -> Option<[u8; N]> {
+        if self.len() < N {
+            return None;
+        }
+        let mut bytes = [0; N];
+        for byte in bytes.iter_mut() {
+            *byte = self.pop_front().unwrap();
+        }
+        Some(bytes)
+    }
+
+    /// Read bytes from `reader` into the free space after the last element.
+    ///
+    /// Performs a single call to [`Read::read`] with the contiguous run of free
+    /// slots following the back of the deque, and appends the bytes read.
+    /// If the free space wraps around, only the first run is filled; call it
+    /// again to fill the rest.
+    ///
+    /// Return the number of bytes appended, which is `0` if the deque is full
+    /// or the reader reached end of file.
+    ///
+    /// [`Read::read`]: std::io::Read::read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// let mut reader: &[u8] = b"abcdef";
+    ///
+    /// assert_eq!(buf.read_from(&mut reader).unwrap(), 4);
+    /// assert_eq!(buf.read_from(&mut reader).unwrap(), 0);
+    /// assert_eq!(buf, (*b"abcd").into());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from<R: std::io::Read>(&mut self, reader: &mut R) -> std::io::Result<usize> {
+        let spare = self.spare_capacity_mut();
+        let free = spare.len();
+        let spare = unsafe {
+            // `Read` implementations may read from the buffer, so it must be initialized
+            ptr::write_bytes(spare.as_mut_ptr(), 0, free);
+            slice_assume_init_mut(spare)
+        };
+
+        let read = reader.read(spare)?;
+        assert!(
+            read <= free,
+            "reader reported more bytes than the buffer holds"
+        );
+        unsafe {
+            let len = self.len();
+            self.set_len(len + read);
+        }
+        Ok(read)
+    }
+
+    /// Write the front contiguous run of bytes to `writer` and remove them.
+    ///
+    /// Performs a single call to [`Write::write`] with the first slice of
+    /// [`as_slices`](#method.as_slices), and removes as many bytes from the
+    /// front as were written. Call it until it returns `0` to drain the deque.
+    ///
+    /// Return the number of bytes removed.
+    ///
+    /// [`Write::write`]: std::io::Write::write
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arraydeque::ArrayDeque;
+    ///
+    /// let mut buf: ArrayDeque<u8, 4> = ArrayDeque::new();
+    /// buf.extend_back(*b"cd");
+    /// buf.extend_front(*b"ba");
+    ///
+    /// let mut out = Vec::new();
+    /// while buf.write_to(&mut out).unwrap() > 0 {}
+    ///
+    /// assert!(buf.is_empty());
+    /// assert_eq!(out, b"abcd");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&mut self, writer: &mut W) -> std::io::Result<usize> {
+        let front = self.as_slices().0;
+        let written = writer.write(front)?;
+        assert!(
+            written <= front.len(),
+            "writer reported more bytes than the buffer holds"
+        );
+        unsafe {
+            let tail = self.tail();
+            let len = self.len();
+            self.set_tail(Self::wrap_add(tail, written));
+            self.set_len(len - written);
+        }
+        Ok(written)
+    }
+}
+
+/// Copy of currently-unstable `MaybeUninit::slice_assume_init_ref`.
+unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
+    // SAFETY: casting `slice` to a `*const [T]` is safe since the caller guarantees that
+    // `slice` is initialized, and `MaybeUninit` is guaranteed to have the same layout as `T`.
+    // The pointer obtained is valid since it refers to memory owned by `slice` which is a
+    // reference and thus guaranteed to be valid for reads.
+    &*(slice as *const [MaybeUninit<T>] as *const [T])
+}
+
+/// Copy of currently-unstable `MaybeUninit::slice_assume_init_mut`.
+unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
+    // SAFETY: similar to safety notes for `slice_assume_init_ref`, but we have a
+    // mutable reference which is also guaranteed to be valid for writes.
+    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
+}
+
+/// Collect the first `M` references yielded by `iter`.
+///
+/// Panics if `iter` has fewer than `M` elements left, as the array would not
+/// be fully initialized.
+fn array_of_refs<T, const M: usize>(iter: Iter<'_, T>) -> [&T; M] {
+    assert!(iter.len() >= M, "not enough elements for the array");
+    let mut refs = [MaybeUninit::<&T>::uninit(); M];
+    for (slot, element) in refs.iter_mut().zip(iter) {
+        *slot = MaybeUninit::new(element);
+    }
+    // SAFETY: all `M` slots were written, and `MaybeUninit<&T>` has the same
+    // layout as `&T`.
+    unsafe { ptr::read(refs.as_ptr().cast()) }
+}
+
+impl<T, const CAP: usize> From<ArrayDeque<T, CAP, Wrapping>> for ArrayDeque<T, CAP, Saturating> {
+    fn from(buf: ArrayDeque<T, CAP, Wrapping>) -> Self {
+        buf.into_behavior()
+    }
+}
+
+impl<T, const CAP: usize> From<ArrayDeque<T, CAP, Saturating>> for ArrayDeque<T, CAP, Wrapping> {
+    fn from(buf: ArrayDeque<T, CAP, Saturating>) -> Self {
+        buf.into_behavior()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const CAP: usize, B: Behavior> From<Vec<T>> for ArrayDeque<T, CAP, B>
+where
+    Self: FromIterator<T>,
+{
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T, const CAP: usize, const N: usize, B: Behavior> From<[T; N]> for ArrayDeque<T, CAP, B>
+where
+    Self: FromIterator<T>,
+{
+    fn from(arr: [T; N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const CAP: usize, B: Behavior> From<ArrayDeque<T, CAP, B>> for Vec<T>
+where
+    Self: FromIterator<T>,
+{
+    fn from(deque: ArrayDeque<T, CAP, B>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+/// Clones the elements into a new `Vec`, front to back, leaving the deque
+/// untouched. Same as [`ArrayDeque::to_vec`].
+///
+/// ```
+/// use arraydeque::ArrayDeque;
+///
+/// let buf: ArrayDeque<_, 3> = [1, 2, 3].into();
+/// let vec = Vec::from(&buf);
+///
+/// assert_eq!(vec, [1, 2, 3]);
+/// assert_eq!(buf.len(), 3);
+/// ```
+#[cfg(feature = "alloc")]
+impl<T, const CAP: usize, B: Behavior> From<&ArrayDeque<T, CAP, B>> for Vec<T>
+where
+    T: Clone,
+{
+    fn from(deque: &ArrayDeque<T, CAP, B>) -> Self {
+        deque.to_vec()
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Drop for ArrayDeque<T, CAP, B> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Default for ArrayDeque<T, CAP, B> {
+    #[inline]
+    fn default() -> Self {
+        ArrayDeque::new()
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> PartialEq for ArrayDeque<T, CAP, B>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.contents_eq(other)
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Eq for ArrayDeque<T, CAP, B> where T: Eq {}
+
+impl<T, const CAP: usize, B: Behavior> PartialOrd for ArrayDeque<T, CAP, B>
+where
+    T: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Ord for ArrayDeque<T, CAP, B>
+where
+    T: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Hash for ArrayDeque<T, CAP, B>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash element by element, so that the calls to the hasher don't
+        // depend on where the elements wrap around. With a streaming hasher
+        // like `DefaultHasher` this hashes like a slice with the same contents,
+        // but not with hashers that are sensitive to `write` boundaries.
+        self.len().hash(state);
+        for element in self {
+            element.hash(state);
+        }
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> Index<usize> for ArrayDeque<T, CAP, B> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index)
+            .or_else(|| {
+                panic!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    len, index
+                )
+            })
+            .unwrap()
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> IndexMut<usize> for ArrayDeque<T, CAP, B> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index)
+            .or_else(|| {
+                panic!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    len, index
+                )
+            })
+            .unwrap()
+    }
+}
+
+/// Indexing by a range returns a single slice, so the range must not span the
+/// wrapping point of the ring buffer. Call `linearize` first to guarantee it.
+impl<T, const CAP: usize, B: Behavior> Index<Range<usize>> for ArrayDeque<T, CAP, B> {
+    type Output = [T];
+
+    #[inline]
+    fn index(&self, range: Range<usize>) -> &[T] {
+        let len = self.len();
+        match self.get_range(range.clone()) {
+            Some((a, [])) => a,
+            Some(_) => panic!(
+                "range {:?} is not contiguous, call `linearize` first",
+                range
+            ),
+            None => panic!(
+                "range out of bounds: the len is {} but the range is {:?}",
+                len, range
+            ),
+        }
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> IndexMut<Range<usize>> for ArrayDeque<T, CAP, B> {
+    #[inline]
+    fn index_mut(&mut self, range: Range<usize>) -> &mut [T] {
+        let len = self.len();
+        match self.get_range_mut(range.clone()) {
+            Some((a, [])) => a,
+            Some(_) => panic!(
+                "range {:?} is not contiguous, call `linearize` first",
+                range
+            ),
+            None => panic!(
+                "range out of bounds: the len is {} but the range is {:?}",
+                len, range
+            ),
+        }
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> IntoIterator for ArrayDeque<T, CAP, B> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP, B>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self }
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> IntoIterator for &'a ArrayDeque<T, CAP, B> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> IntoIterator for &'a mut ArrayDeque<T, CAP, B> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> fmt::Debug for ArrayDeque<T, CAP, B>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+struct DisplayJoined<'a, T> {
+    iter: Iter<'a, T>,
+    sep: &'a str,
+}
+
+impl<'a, T> fmt::Display for DisplayJoined<'a, T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut iter = self.iter.clone();
+        if let Some(first) = iter.next() {
+            first.fmt(f)?;
+            for element in iter {
+                f.write_str(self.sep)?;
+                element.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the bytes as lowercase hex digits, two per byte, with a `0x`
+/// prefix in the alternate form.
+///
+/// ```
+/// use arraydeque::ArrayDeque;
+///
+/// let buf: ArrayDeque<u8, 4> = [0x0a, 0xbc, 0x01].into();
+///
+/// assert_eq!(format!("{:x}", buf), "0abc01");
+/// assert_eq!(format!("{:#x}", buf), "0x0abc01");
+/// ```
+impl<const CAP: usize, B: Behavior> fmt::LowerHex for ArrayDeque<u8, CAP, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        let (a, b) = self.as_slices();
+        for byte in a.iter().chain(b) {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the bytes as uppercase hex digits, two per byte, with a `0x`
+/// prefix in the alternate form.
+///
+/// ```
+/// use arraydeque::ArrayDeque;
+///
+/// let buf: ArrayDeque<u8, 4> = [0x0a, 0xbc, 0x01].into();
+///
+/// assert_eq!(format!("{:X}", buf), "0ABC01");
+/// assert_eq!(format!("{:#X}", buf), "0x0ABC01");
+/// ```
+impl<const CAP: usize, B: Behavior> fmt::UpperHex for ArrayDeque<u8, CAP, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        let (a, b) = self.as_slices();
+        for byte in a.iter().chain(b) {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+// When `capacity` is a power of two the wrap is a mask, otherwise a single
+// conditional subtraction, as `index` is always in bounds. Callers passing the
+// `CAP` const parameter get the check folded away at compile time.
+//
+// This keeps `head()` cheap enough that storing `tail` and `len` costs about
+// the same as storing `head` and `tail`, without reserving a slot or keeping a
+// separate full flag to tell a full deque from an empty one.
+#[inline]
+fn wrap_add(index: usize, addend: usize, capacity: usize) -> usize {
+    debug_assert!(index < capacity || capacity == 0);
+    debug_assert!(addend <= capacity);
+    if capacity.is_power_of_two() {
+        wrap_add_pow2(index, addend, capacity)
+    } else {
+        let sum = index + addend;
+        if sum >= capacity {
+            sum - capacity
+        } else {
+            sum
+        }
+    }
+}
+
+#[inline]
+fn wrap_sub(index: usize, subtrahend: usize, capacity: usize) -> usize {
+    debug_assert!(index < capacity || capacity == 0);
+    debug_assert!(subtrahend <= capacity);
+    if capacity.is_power_of_two() {
+        wrap_sub_pow2(index, subtrahend, capacity)
+    } else if index >= subtrahend {
+        index - subtrahend
+    } else {
+        index + capacity - subtrahend
+    }
+}
+
+// Branchless variants for power-of-two capacities. Wrapping arithmetic is
+// correct modulo any power of two, so no correction term is needed.
+#[inline]
+fn wrap_add_pow2(index: usize, addend: usize, capacity: usize) -> usize {
+    debug_assert!(capacity.is_power_of_two());
+    index.wrapping_add(addend) & (capacity - 1)
+}
+
+#[inline]
+fn wrap_sub_pow2(index: usize, subtrahend: usize, capacity: usize) -> usize {
+    debug_assert!(capacity.is_power_of_two());
+    index.wrapping_sub(subtrahend) & (capacity - 1)
+}
+
+/// `ArrayDeque` iterator
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T: 'a> {
+    ring: &'a [MaybeUninit<T>],
+    tail: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let tail = self.tail;
+        self.tail = wrap_add(self.tail, 1, self.ring.len());
+        self.len -= 1;
+        unsafe { Some(self.ring.get_unchecked(tail).assume_init_ref()) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    // `try_fold` cannot be overridden on stable, so the short-circuiting
+    // adapters built on it scan the two runs directly instead
+
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        let mut taken = 0;
+        let all = a.iter().chain(b).all(|x| {
+            taken += 1;
+            f(x)
+        });
+        self.advance(taken);
+        all
+    }
+
+    fn any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        let mut taken = 0;
+        let any = a.iter().chain(b).any(|x| {
+            taken += 1;
+            f(x)
+        });
+        self.advance(taken);
+        any
+    }
+
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let (a, b) = self.as_slices();
+        let mut taken = 0;
+        let found = a.iter().chain(b).find(|x| {
+            taken += 1;
+            predicate(x)
+        });
+        self.advance(taken);
+        found
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let head = wrap_add(self.tail, self.len, self.ring.len());
+        unsafe { Some(self.ring.get_unchecked(head).assume_init_ref()) }
+    }
+
+    fn rfold<Acc, F>(self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.as_slices();
+        let acc = b.iter().rfold(init, &mut f);
+        a.iter().rfold(acc, f)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> Iter<'a, T> {
+    /// Skip the next `n` elements.
+    fn advance(&mut self, n: usize) {
+        debug_assert!(n <= self.len);
+        self.tail = wrap_add(self.tail, n, self.ring.len());
+        self.len -= n;
+    }
+
+    /// The remaining elements as the two contiguous runs of the ring.
+    fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        let first = cmp::min(self.len, self.ring.len() - self.tail);
+        unsafe {
+            (
+                slice_assume_init_ref(&self.ring[self.tail..self.tail + first]),
+                slice_assume_init_ref(&self.ring[..self.len - first]),
+            )
+        }
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            ring: self.ring,
+            tail: self.tail,
+            len: self.len,
+        }
+    }
+}
+
+/// Infinitely repeating `ArrayDeque` iterator
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Cycle<'a, T: 'a> {
+    ring: &'a [MaybeUninit<T>],
+    tail: usize,
+    len: usize,
+    index: usize,
+}
+
+impl<'a, T> Iterator for Cycle<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = wrap_add(self.tail, self.index, self.ring.len());
+        self.index += 1;
+        if self.index == self.len {
+            self.index = 0;
+        }
+        unsafe { Some(self.ring.get_unchecked(idx).assume_init_ref()) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.len == 0 {
+            (0, Some(0))
+        } else {
+            (usize::MAX, None)
+        }
+    }
+}
+
+impl<'a, T> Clone for Cycle<'a, T> {
+    fn clone(&self) -> Self {
+        Cycle {
+            ring: self.ring,
+            tail: self.tail,
+            len: self.len,
+            index: self.index,
+        }
+    }
+}
+
+/// `ArrayDeque` mutable iterator
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T: 'a> {
+    ring: &'a mut [MaybeUninit<T>],
+    tail: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let tail = self.tail;
+        self.tail = wrap_add(self.tail, 1, self.ring.len());
+        self.len -= 1;
+        unsafe {
+            let elem = self.ring.get_unchecked_mut(tail).assume_init_mut();
+            Some(std::mem::transmute::<&mut T, &'a mut T>(elem))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let head = wrap_add(self.tail, self.len, self.ring.len());
+        unsafe {
+            let elem = self.ring.get_unchecked_mut(head).assume_init_mut();
+            Some(std::mem::transmute::<&mut T, &'a mut T>(elem))
+        }
+    }
+
+    fn rfold<Acc, F>(self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let (a, b) = self.into_slices();
+        let acc = b.iter_mut().rfold(init, &mut f);
+        a.iter_mut().rfold(acc, f)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> IterMut<'a, T> {
+    /// The remaining elements as the two contiguous runs of the ring.
+    fn into_slices(self) -> (&'a mut [T], &'a mut [T]) {
+        let first = cmp::min(self.len, self.ring.len() - self.tail);
+        let (left, right) = self.ring.split_at_mut(self.tail);
+        unsafe {
+            (
+                slice_assume_init_mut(&mut right[..first]),
+                slice_assume_init_mut(&mut left[..self.len - first]),
+            )
+        }
+    }
+}
+
+/// Front-to-back iterator over mutable chunks of an `ArrayDeque`
+///
+/// Created by [`ArrayDeque::chunks_mut`].
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunksMut<'a, T: 'a> {
+    front: &'a mut [T],
+    back: &'a mut [T],
+    chunk_size: usize,
+}
+
+impl<'a, T> Iterator for ChunksMut<'a, T> {
+    type Item = (&'a mut [T], &'a mut [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.is_empty() {
+            self.front = mem::take(&mut self.back);
+        }
+        let front = mem::take(&mut self.front);
+        if front.is_empty() {
+            return None;
+        }
+        if front.len() >= self.chunk_size {
+            let (chunk, rest) = front.split_at_mut(self.chunk_size);
+            self.front = rest;
+            return Some((chunk, &mut []));
+        }
+
+        // The chunk wraps around: it ends with the start of `back`
+        let back = mem::take(&mut self.back);
+        let mid = cmp::min(self.chunk_size - front.len(), back.len());
+        let (chunk, rest) = back.split_at_mut(mid);
+        self.front = rest;
+        Some((front, chunk))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        let count = match len % self.chunk_size {
+            0 => len / self.chunk_size,
+            _ => len / self.chunk_size + 1,
+        };
+        (count, Some(count))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ChunksMut<'a, T> {}
+
+/// Back-to-front iterator over mutable chunks of an `ArrayDeque`
+///
+/// Created by [`ArrayDeque::rchunks_mut`].
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct RChunksMut<'a, T: 'a> {
+    front: &'a mut [T],
+    back: &'a mut [T],
+    chunk_size: usize,
+}
+
+impl<'a, T> Iterator for RChunksMut<'a, T> {
+    type Item = (&'a mut [T], &'a mut [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            self.back = mem::take(&mut self.front);
+        }
+        let back = mem::take(&mut self.back);
+        if back.is_empty() {
+            return None;
+        }
+        if back.len() >= self.chunk_size || self.front.is_empty() {
+            let mid = back.len().saturating_sub(self.chunk_size);
+            let (rest, chunk) = back.split_at_mut(mid);
+            self.back = rest;
+            return Some((chunk, &mut []));
+        }
+
+        // The chunk wraps around: it starts with the end of `front`
+        let front = mem::take(&mut self.front);
+        let mid = front.len() - cmp::min(self.chunk_size - back.len(), front.len());
+        let (rest, chunk) = front.split_at_mut(mid);
+        self.back = rest;
+        Some((chunk, back))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        let count = match len % self.chunk_size {
+            0 => len / self.chunk_size,
+            _ => len / self.chunk_size + 1,
+        };
+        (count, Some(count))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RChunksMut<'a, T> {}
+
+/// Iterator over runs of an `ArrayDeque` separated by a predicate
+///
+/// Created by [`ArrayDeque::chunk_by`].
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ChunkBy<'a, T: 'a, F> {
+    front: &'a [T],
+    back: &'a [T],
+    same: F,
+}
+
+impl<'a, T, F> Iterator for ChunkBy<'a, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = (&'a [T], &'a [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front.is_empty() {
+            self.front = mem::take(&mut self.back);
+        }
+        let (front, back) = (self.front, self.back);
+        let mut elements = front.iter().chain(back);
+        let mut prev = elements.next()?;
+        let mut len = 1;
+        for element in elements {
+            if !(self.same)(prev, element) {
+                break;
+            }
+            prev = element;
+            len += 1;
+        }
+
+        if len <= front.len() {
+            let (run, rest) = front.split_at(len);
+            self.front = rest;
+            return Some((run, &[]));
+        }
+
+        // The run wraps around: it ends with the start of `back`
+        let (run, rest) = back.split_at(len - front.len());
+        self.front = rest;
+        self.back = &[];
+        Some((front, run))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (cmp::min(len, 1), Some(len))
+    }
+}
+
+/// Iterator over runs of an `ArrayDeque` separated by matching elements
+///
+/// Created by [`ArrayDeque::split_by`].
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct SplitBy<'a, T: 'a, P> {
+    front: &'a [T],
+    back: &'a [T],
+    pred: P,
+    finished: bool,
+}
+
+impl<'a, T, P> Iterator for SplitBy<'a, T, P>
+where
+    P: FnMut(&T) -> bool,
+{
+    type Item = (&'a [T], &'a [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if self.front.is_empty() {
+            self.front = mem::take(&mut self.back);
+        }
+        let (front, back) = (self.front, self.back);
+        let index = match front.iter().chain(back).position(&mut self.pred) {
+            Some(index) => index,
+            None => {
+                self.finished = true;
+                return Some((front, back));
+            }
+        };
+
+        if index < front.len() {
+            self.front = &front[index + 1..];
+            return Some((&front[..index], &[]));
+        }
+
+        // The run wraps around: it ends with the start of `back`
+        let mid = index - front.len();
+        self.front = &back[mid + 1..];
+        self.back = &[];
+        Some((front, &back[..mid]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.front.len() + self.back.len() + 1))
+        }
+    }
+}
+
+/// By-value `ArrayDeque` iterator
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct IntoIter<T, const CAP: usize, B: Behavior> {
+    inner: ArrayDeque<T, CAP, B>,
+}
+
+impl<T, const CAP: usize, B: Behavior> Iterator for IntoIter<T, CAP, B> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+
+    fn fold<Acc, F>(mut self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, T) -> Acc,
+    {
+        if mem::needs_drop::<T>() {
+            let mut acc = init;
+            while let Some(element) = self.inner.pop_front() {
+                acc = f(acc, element);
+            }
+            return acc;
+        }
+
+        // Elements need no dropping, so the deque can be emptied up front and
+        // read straight out of its two slices, even if `f` panics.
+        let (a, b) = self.inner.as_slices();
+        let (a_ptr, a_len) = (a.as_ptr(), a.len());
+        let (b_ptr, b_len) = (b.as_ptr(), b.len());
+        unsafe {
+            self.inner.set_len(0);
+        }
+
+        let mut acc = init;
+        unsafe {
+            for i in 0..a_len {
+                acc = f(acc, ptr::read(a_ptr.add(i)));
+            }
+            for i in 0..b_len {
+                acc = f(acc, ptr::read(b_ptr.add(i)));
+            }
+        }
+        acc
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> DoubleEndedIterator for IntoIter<T, CAP, B> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.pop_back()
+    }
+}
+
+impl<T, const CAP: usize, B: Behavior> ExactSizeIterator for IntoIter<T, CAP, B> {}
+
+/// Draining `ArrayDeque` iterator
+pub struct Drain<'a, T, const CAP: usize, B>
+where
+    B: Behavior,
+{
+    after_tail: usize,
+    after_len: usize,
+    iter: Iter<'a, T>,
+    deque: *mut ArrayDeque<T, CAP, B>,
+}
+
+impl<'a, T, const CAP: usize, B> Drop for Drain<'a, T, CAP, B>
+where
+    B: Behavior,
+{
+    fn drop(&mut self) {
+        // Drop the remaining elements in place rather than reading them out
+        let tail = self.iter.tail;
+        let len = self.iter.len;
+        self.iter.len = 0;
+        unsafe {
+            let xs = (*self.deque).ptr_mut();
+            let first = cmp::min(len, CAP - tail);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(xs.add(tail), first));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(xs, len - first));
+        }
+
+        let source_deque = unsafe { &mut *self.deque };
+
+        let tail_len = source_deque.len();
+        let head_len = self.after_len;
+
+        let orig_tail = source_deque.tail();
+        let drain_tail = wrap_add(orig_tail, tail_len, CAP);
+        let drain_head = self.after_tail;
+        let orig_head = wrap_add(drain_head, head_len, CAP);
+        let orig_len = wrap_sub(orig_head, orig_tail, CAP);
+
+        // Restore the original len value
+        unsafe { source_deque.set_len(orig_len) }
+        match (tail_len, head_len) {
+            (0, 0) => unsafe {
+                source_deque.set_tail(0);
+                source_deque.set_len(0);
+            },
+            (0, _) => unsafe {
+                source_deque.set_tail(drain_head);
+                source_deque.set_len(head_len);
+            },
+            (_, 0) => unsafe { source_deque.set_len(tail_len) },
+            _ => unsafe {
+                if tail_len <= head_len {
+                    let new_tail = wrap_sub(drain_head, tail_len, CAP);
+                    source_deque.set_tail(new_tail);
+                    source_deque.set_len(tail_len + head_len);
+                    source_deque.wrap_copy(new_tail, orig_tail, tail_len);
+                } else {
+                    source_deque.set_len(tail_len + head_len);
+                    source_deque.wrap_copy(drain_tail, drain_head, head_len);
+                }
+            },
+        }
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> Iterator for Drain<'a, T, CAP, B> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|elt| unsafe { ptr::read(elt) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> DoubleEndedIterator for Drain<'a, T, CAP, B> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|elt| unsafe { ptr::read(elt) })
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> ExactSizeIterator for Drain<'a, T, CAP, B> {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_must_use)]
+    use super::*;
+
+    #[test]
+    fn test_simple() {
+        macro_rules! test {
+            ($behavior:ident) => {{
+                let mut tester: ArrayDeque<_, 7, $behavior> = ArrayDeque::new();
+                assert_eq!(tester.capacity(), 7);
+                assert_eq!(tester.len(), 0);
+
+                tester.push_back(1);
+                tester.push_back(2);
+                tester.push_back(3);
+                tester.push_back(4);
+                assert_eq!(tester.len(), 4);
+                assert_eq!(tester.pop_front(), Some(1));
+                assert_eq!(tester.pop_front(), Some(2));
+                assert_eq!(tester.len(), 2);
+                assert_eq!(tester.pop_front(), Some(3));
+                assert_eq!(tester.pop_front(), Some(4));
+                assert_eq!(tester.pop_front(), None);
+            }};
+        }
+
+        test!(Saturating);
+        test!(Wrapping);
+    }
+
+    #[test]
+    fn test_simple_reversely() {
+        macro_rules! test {
+            ($behavior:ident) => {{
+                let mut tester: ArrayDeque<_, 7, $behavior> = ArrayDeque::new();
+                assert_eq!(tester.capacity(), 7);
+                assert_eq!(tester.len(), 0);
+
+                tester.push_front(1);
+                tester.push_front(2);
+                tester.push_front(3);
+                tester.push_front(4);
+                assert_eq!(tester.len(), 4);
+                assert_eq!(tester.pop_back(), Some(1));
+                assert_eq!(tester.pop_back(), Some(2));
+                assert_eq!(tester.len(), 2);
+                assert_eq!(tester.pop_back(), Some(3));
+                assert_eq!(tester.pop_back(), Some(4));
+                assert_eq!(tester.pop_back(), None);
+            }};
+        }
+
+        test!(Saturating);
+        test!(Wrapping);
+    }
+
+    #[test]
+    fn test_overflow_saturating() {
+        let mut tester: ArrayDeque<_, 2, Saturating> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), Ok(()));
+        assert_eq!(tester.push_back(2), Ok(()));
+        assert_eq!(tester.push_back(3), Err(CapacityError { element: 3 }));
+
+        let mut tester: ArrayDeque<_, 2, Saturating> = ArrayDeque::new();
+        assert_eq!(tester.insert(0, 1), Ok(()));
+        assert_eq!(tester.insert(1, 2), Ok(()));
+        assert_eq!(tester.insert(2, 3), Err(CapacityError { element: 3 }));
+    }
 
-        let idx = Self::wrap_add(self.tail(), index);
+    #[test]
+    fn test_overflow_wrapping() {
+        let mut tester: ArrayDeque<_, 2, Wrapping> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), None);
+        assert_eq!(tester.push_back(2), None);
+        assert_eq!(tester.push_back(3), Some(1));
+    }
 
-        let elem = unsafe { Some(self.buffer_read(idx)) };
+    #[test]
+    fn test_pop_empty() {
+        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
+        assert_eq!(tester.push_back(1), Ok(()));
+        assert_eq!(tester.pop_front(), Some(1));
+        assert!(tester.is_empty());
+        assert_eq!(tester.len(), 0);
+        assert_eq!(tester.pop_front(), None);
+    }
 
-        let distance_to_tail = index;
-        let distance_to_head = self.len() - index;
+    #[test]
+    fn test_index() {
+        let mut tester: ArrayDeque<_, 3> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        tester.push_back(3);
+        assert_eq!(tester[0], 1);
+        // pop_front 1 <- [2, 3]
+        assert_eq!(tester.pop_front(), Some(1));
+        assert_eq!(tester[0], 2);
+        assert_eq!(tester.len(), 2);
+        // push_front 0 -> [0, 2, 3]
+        tester.push_front(0);
+        assert_eq!(tester[0], 0);
+        // [0, 2] -> 3 pop_back
+        assert_eq!(tester.pop_back(), Some(3));
+        assert_eq!(tester[1], 2);
+    }
 
-        let contiguous = self.is_contiguous();
+    #[test]
+    #[should_panic]
+    fn test_index_overflow() {
+        let mut tester: ArrayDeque<_, 3> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        let _ = tester[2];
+    }
 
-        match (
-            contiguous,
-            distance_to_tail <= distance_to_head,
-            idx >= self.tail(),
-        ) {
-            (true, true, _) => {
-                unsafe {
-                    // contiguous, remove closer to tail:
-                    //
-                    //             T   R         H
-                    //      [. . . o o x o o o o . . . . . .]
-                    //
-                    //               T           H
-                    //      [. . . . o o o o o o . . . . . .]
-                    //               M M
+    #[test]
+    fn test_contains_bytes() {
+        const CAP: usize = 64;
+        let mut tester = ArrayDeque::<u8, CAP>::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..CAP as u8 - 1);
+            for x in 0..CAP as u8 - 1 {
+                assert!(tester.contains(&x));
+            }
+            assert!(!tester.contains(&(CAP as u8 - 1)));
+        }
+    }
 
-                    let tail = self.tail();
-                    self.copy(tail + 1, tail, index);
-                    self.set_tail_forward();
-                }
+    #[test]
+    fn test_position() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
             }
-            (true, false, _) => {
-                unsafe {
-                    // contiguous, remove closer to head:
-                    //
-                    //             T       R     H
-                    //      [. . . o o o o x o o . . . . . .]
-                    //
-                    //             T           H
-                    //      [. . . o o o o o o . . . . . . .]
-                    //                     M M
+            tester.extend_back([0, 1, 2, 0, 1, 2]);
+            for x in 0..3 {
+                assert_eq!(tester.position(|&y| y == x), Some(x));
+                assert_eq!(tester.rposition(|&y| y == x), Some(x + 3));
+                assert_eq!(tester.index_of(&x), Some(x));
+                assert_eq!(tester.find(|&y| y >= x), Some(&x));
+            }
+            assert_eq!(tester.index_of(&3), None);
+            assert_eq!(tester.position(|&y| y == 3), None);
+            assert_eq!(tester.rposition(|&y| y == 3), None);
+        }
+    }
 
-                    let head = self.head();
-                    self.copy(idx, idx + 1, head - idx - 1);
-                    self.set_head_backward();
+    #[test]
+    fn test_wrap() {
+        for capacity in 1..=16 {
+            for index in 0..capacity {
+                for offset in 0..=capacity {
+                    assert_eq!(
+                        wrap_add(index, offset, capacity),
+                        (index + offset) % capacity
+                    );
+                    assert_eq!(
+                        wrap_sub(index, offset, capacity),
+                        (index + capacity - offset) % capacity
+                    );
+                    if capacity.is_power_of_two() {
+                        assert_eq!(
+                            wrap_add_pow2(index, offset, capacity),
+                            (index + offset) % capacity
+                        );
+                        assert_eq!(
+                            wrap_sub_pow2(index, offset, capacity),
+                            (index + capacity - offset) % capacity
+                        );
+                    }
                 }
             }
-            (false, true, true) => {
-                unsafe {
-                    // discontiguous, remove closer to tail, tail section:
-                    //
-                    //                   H         T   R
-                    //      [o o o o o o . . . . . o o x o o]
-                    //
-                    //                   H           T
-                    //      [o o o o o o . . . . . . o o o o]
-                    //                               M M
+        }
+    }
 
-                    let tail = self.tail();
-                    self.copy(tail + 1, tail, index);
-                    self.set_tail_forward();
-                }
+    #[test]
+    fn test_index_range() {
+        let mut tester: ArrayDeque<_, 4> = ArrayDeque::new();
+        tester.extend_back([1, 2, 3]);
+        tester.extend_front([0]);
+        assert_eq!(tester[0..1], [0]);
+        assert_eq!(tester[1..3], [1, 2]);
+        assert_eq!(tester[2..2], []);
+
+        tester[1..4].copy_from_slice(&[4, 5, 6]);
+        assert_eq!(tester, [0, 4, 5, 6].into());
+
+        tester.linearize();
+        assert_eq!(tester[0..4], [0, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not contiguous")]
+    fn test_index_range_discontiguous() {
+        let mut tester: ArrayDeque<_, 4> = ArrayDeque::new();
+        tester.extend_back([1, 2, 3]);
+        tester.extend_front([0]);
+        let _ = &tester[0..2];
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        {
+            let mut iter = tester.iter();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(&1));
+            assert_eq!(iter.next(), Some(&2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        tester.pop_front();
+        tester.push_back(3);
+        {
+            let mut iter = (&tester).into_iter();
+            assert_eq!(iter.next(), Some(&2));
+
+            // test clone
+            let mut iter2 = iter.clone();
+            assert_eq!(iter.next(), Some(&3));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter2.next(), Some(&3));
+            assert_eq!(iter2.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        const CAP: usize = 4;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        assert_eq!(tester.cycle().next(), None);
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
             }
-            (false, false, false) => {
-                unsafe {
-                    // discontiguous, remove closer to head, head section:
-                    //
-                    //               R     H           T
-                    //      [o o o o x o o . . . . . . o o o]
-                    //
-                    //                   H             T
-                    //      [o o o o o o . . . . . . . o o o]
-                    //               M M
+            tester.extend_back(0..3);
+            let mut iter = tester.cycle();
+            for i in 0..10 {
+                assert_eq!(iter.next(), Some(&(i % 3)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
+        tester.push_back(1);
+        tester.push_back(2);
+        {
+            let mut iter = tester.iter_mut();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(&mut 1));
+            assert_eq!(iter.next(), Some(&mut 2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        tester.pop_front();
+        tester.push_back(3);
+        {
+            let mut iter = (&mut tester).into_iter();
+            assert_eq!(iter.next(), Some(&mut 2));
+            assert_eq!(iter.next(), Some(&mut 3));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            // mutation
+            let mut iter = tester.iter_mut();
+            *iter.next().unwrap() += 1;
+            *iter.next().unwrap() += 2;
+        }
+        assert_eq!(tester[0], 3);
+        assert_eq!(tester[1], 5);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct NoCopy<T>(T);
+
+        {
+            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.size_hint(), (2, Some(2)));
+            assert_eq!(iter.next(), Some(NoCopy(1)));
+            assert_eq!(iter.next(), Some(NoCopy(2)));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+        {
+            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            tester.pop_front();
+            tester.push_back(NoCopy(3));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.next(), Some(NoCopy(2)));
+            assert_eq!(iter.next(), Some(NoCopy(3)));
+            assert_eq!(iter.next(), None);
+        }
+        {
+            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
+            tester.push_back(NoCopy(1));
+            tester.push_back(NoCopy(2));
+            tester.pop_front();
+            tester.push_back(NoCopy(3));
+            tester.pop_front();
+            tester.push_back(NoCopy(4));
+            let mut iter = tester.into_iter();
+            assert_eq!(iter.next(), Some(NoCopy(3)));
+            assert_eq!(iter.next(), Some(NoCopy(4)));
+            assert_eq!(iter.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_into_iter_fold() {
+        const CAP: usize = 5;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..4);
+
+            let mut iter = tester.clone().into_iter();
+            iter.next();
+            let folded = iter.fold(vec![], |mut v, x| {
+                v.push(x);
+                v
+            });
+            assert_eq!(folded, [1, 2, 3]);
+
+            let strings: ArrayDeque<_, CAP> = tester.iter().map(|x| x.to_string()).collect();
+            let folded = strings.into_iter().fold(String::new(), |s, x| s + &x);
+            assert_eq!(folded, "0123");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_drain() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
 
-                    let head = self.head();
-                    self.copy(idx, idx + 1, head - idx - 1);
-                    self.set_head_backward();
+        for padding in 0..CAP {
+            for drain_start in 0..CAP {
+                for drain_end in drain_start..CAP {
+                    // deque starts from different tail position
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+
+                    tester.extend_back(0..CAP);
+
+                    let mut expected = vec![0, 1, 2, 3, 4, 5, 6, 7];
+                    let drains: Vec<_> = tester.drain(drain_start..drain_end).collect();
+                    let expected_drains: Vec<_> = expected.drain(drain_start..drain_end).collect();
+                    assert_eq!(drains, expected_drains);
+                    assert_eq!(tester, expected.into());
                 }
             }
-            (false, false, true) => {
-                unsafe {
-                    // discontiguous, remove closer to head, tail section:
-                    //
-                    //             H           T         R
-                    //      [o o o . . . . . . o o o o o x o]
-                    //
-                    //           H             T
-                    //      [o o . . . . . . . o o o o o o o]
-                    //       M M                         M M
-                    //
-                    // or quasi-discontiguous, remove next to head, tail section:
-                    //
-                    //       H                 T         R
-                    //      [. . . . . . . . . o o o o o x o]
-                    //
-                    //                         T           H
-                    //      [. . . . . . . . . o o o o o o .]
-                    //                                   M
-
-                    // draw in elements in the tail section
-                    self.copy(idx, idx + 1, CAP - idx - 1);
+        }
+    }
 
-                    // Prevents underflow.
-                    if self.head() != 0 {
-                        // copy first element into empty spot
-                        self.copy(CAP - 1, 0, 1);
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_drain_inclusive() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
 
-                        // move elements in the head section backwards
-                        let head = self.head();
-                        self.copy(0, 1, head - 1);
+        for padding in 0..CAP {
+            for drain_start in 0..CAP {
+                for drain_end in drain_start..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
                     }
 
-                    self.set_head_backward();
+                    tester.extend_back(0..CAP);
+
+                    let mut expected = vec![0, 1, 2, 3, 4, 5, 6, 7];
+                    let drains: Vec<_> = tester.drain(drain_start..=drain_end).collect();
+                    let expected_drains: Vec<_> = expected.drain(drain_start..=drain_end).collect();
+                    assert_eq!(drains, expected_drains);
+                    assert_eq!(tester, expected.into());
                 }
             }
-            (false, true, false) => {
-                unsafe {
-                    // discontiguous, remove closer to tail, head section:
-                    //
-                    //           R               H     T
-                    //      [o o x o o o o o o o . . . o o o]
-                    //
-                    //                           H       T
-                    //      [o o o o o o o o o o . . . . o o]
-                    //       M M M                       M M
+        }
 
-                    let tail = self.tail();
-                    // draw in elements up to idx
-                    self.copy(1, 0, idx);
+        // An exhausted range is empty, whatever its bounds
+        let mut tester: ArrayDeque<_, CAP> = (0..CAP).collect();
+        let mut range = 2..=2;
+        assert_eq!(range.next(), Some(2));
+        assert_eq!(tester.drain(range.clone()).count(), 0);
+        assert_eq!(tester.remove_range(range), 0);
+        let mut range = 1..=3;
+        range.by_ref().for_each(drop);
+        assert_eq!(tester.drain(range).count(), 0);
+        assert_eq!(tester.len(), CAP);
+    }
 
-                    // copy last element into empty spot
-                    self.copy(0, CAP - 1, 1);
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_drain_to() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
 
-                    // move elements from tail to end forward, excluding the last one
-                    self.copy(tail + 1, tail, CAP - tail - 1);
+        for padding in 0..CAP {
+            for drain_end in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..CAP);
+                let mut expected = vec![0, 1, 2, 3, 4, 5, 6, 7];
+                let drains: Vec<_> = tester.drain(..drain_end).collect();
+                let expected_drains: Vec<_> = expected.drain(..drain_end).collect();
+                assert_eq!(drains, expected_drains);
+                assert_eq!(tester, expected.into());
 
-                    self.set_tail_forward();
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
                 }
+                tester.extend_back(0..CAP);
+                let mut expected = vec![0, 1, 2, 3, 4, 5, 6, 7];
+                let drains: Vec<_> = tester.drain(..=drain_end).collect();
+                let expected_drains: Vec<_> = expected.drain(..=drain_end).collect();
+                assert_eq!(drains, expected_drains);
+                assert_eq!(tester, expected.into());
             }
         }
-
-        elem
     }
 
-    /// Splits the collection into two at the given index.
-    ///
-    /// Returns a newly allocated `Self`. `self` contains elements `[0, at)`,
-    /// and the returned `Self` contains elements `[at, len)`.
-    ///
-    /// Element at index 0 is the front of the queue.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `at > len`
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 3> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    /// buf.push_back(2);
-    ///
-    /// // buf = [0], buf2 = [1, 2]
-    /// let buf2 = buf.split_off(1);
-    ///
-    /// assert_eq!(buf.len(), 1);
-    /// assert_eq!(buf2.len(), 2);
-    /// ```
-    #[track_caller]
-    #[inline]
-    pub fn split_off(&mut self, at: usize) -> Self {
-        let len = self.len();
-        assert!(at <= len, "`at` out of bounds");
+    #[test]
+    fn test_remove_range_drop() {
+        use std::rc::Rc;
 
-        let other_len = len - at;
-        let mut other = Self::new();
+        const CAP: usize = 8;
+        let elem = Rc::new(());
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            for start in 0..CAP + 1 {
+                for end in start..CAP + 1 {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back((0..CAP).map(|i| (i, elem.clone())));
+                    assert_eq!(tester.remove_range(start..end), end - start);
+                    assert_eq!(Rc::strong_count(&elem), 1 + CAP - (end - start));
+                    assert!(tester.iter().map(|x| x.0).eq((0..start).chain(end..CAP)));
+                    tester.clear();
+                }
+            }
+        }
+    }
 
-        unsafe {
-            let (first_half, second_half) = self.as_slices();
+    #[test]
+    fn test_drop() {
+        use std::cell::Cell;
 
-            let first_len = first_half.len();
-            let second_len = second_half.len();
-            if at < first_len {
-                // `at` lies in the first half.
-                let amount_in_first = first_len - at;
+        let flag = &Cell::new(0);
 
-                ptr::copy_nonoverlapping(
-                    first_half.as_ptr().add(at),
-                    other.ptr_mut(),
-                    amount_in_first,
-                );
+        struct Bump<'a>(&'a Cell<i32>);
 
-                // just take all of the second half.
-                ptr::copy_nonoverlapping(
-                    second_half.as_ptr(),
-                    other.ptr_mut().add(amount_in_first),
-                    second_len,
-                );
-            } else {
-                // `at` lies in the second half, need to factor in the elements we skipped
-                // in the first half.
-                let offset = at - first_len;
-                let amount_in_second = second_len - offset;
-                ptr::copy_nonoverlapping(
-                    second_half.as_ptr().add(offset),
-                    other.ptr_mut(),
-                    amount_in_second,
-                );
+        impl<'a> Drop for Bump<'a> {
+            fn drop(&mut self) {
+                let n = self.0.get();
+                self.0.set(n + 1);
             }
         }
 
-        // Cleanup where the ends of the buffers are
-        unsafe {
-            self.set_len(at);
-            other.set_len(other_len);
+        {
+            let mut tester = ArrayDeque::<Bump, 128>::new();
+            tester.push_back(Bump(flag));
+            tester.push_back(Bump(flag));
         }
+        assert_eq!(flag.get(), 2);
 
-        other
+        // test something with the nullable pointer optimization
+        flag.set(0);
+        {
+            let mut tester = ArrayDeque::<_, 3>::new();
+            tester.push_back(vec![Bump(flag)]);
+            tester.push_back(vec![Bump(flag), Bump(flag)]);
+            tester.push_back(vec![]);
+            tester.push_back(vec![Bump(flag)]);
+            assert_eq!(flag.get(), 1);
+            drop(tester.pop_back());
+            assert_eq!(flag.get(), 1);
+            drop(tester.pop_back());
+            assert_eq!(flag.get(), 3);
+        }
+        assert_eq!(flag.get(), 4);
     }
 
-    /// Retains only the elements specified by the predicate.
-    ///
-    /// In other words, remove all elements `e` such that `f(&e)` returns false.
-    /// This method operates in place and preserves the order of the retained
-    /// elements.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 4> = ArrayDeque::new();
-    ///
-    /// buf.extend_back(0..4);
-    /// buf.retain(|&x| x % 2 == 0);
-    ///
-    /// assert_eq!(buf, [0, 2].into());
-    /// ```
-    pub fn retain<F>(&mut self, mut f: F)
-    where
-        F: FnMut(&T) -> bool,
-    {
-        let len = self.len();
-        let mut del = 0;
-        for i in 0..len {
-            if !f(&self[i]) {
-                del += 1;
-            } else if del > 0 {
-                self.swap(i - del, i);
+    #[test]
+    fn test_as_slice() {
+        const CAP: usize = 10;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                // deque starts from different tail position
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+
+                let mut expected = vec![];
+                tester.extend_back(0..len);
+                expected.extend(0..len);
+
+                let split_idx = CAP - padding;
+                if split_idx < len {
+                    assert_eq!(tester.as_slices(), expected[..].split_at(split_idx));
+                } else {
+                    assert_eq!(tester.as_slices(), (&expected[..], &[][..]));
+                }
             }
         }
-        if del > 0 {
-            for _ in (len - del)..self.len() {
-                self.pop_back();
+    }
+
+    #[test]
+    fn test_front_back_n() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).collect();
+                for n in 0..CAP + 2 {
+                    let taken = cmp::min(n, len);
+
+                    let (a, b) = tester.front_n(n);
+                    assert_eq!([a, b].concat(), &expected[..taken]);
+
+                    let (a, b) = tester.back_n(n);
+                    assert_eq!([a, b].concat(), &expected[len - taken..]);
+                }
             }
         }
     }
 
-    /// Returns a pair of slices which contain, in order, the contents of the
-    /// `ArrayDeque`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 7> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    ///
-    /// assert_eq!(buf.as_slices(), (&[0, 1][..], &[][..]));
-    ///
-    /// buf.push_front(2);
-    ///
-    /// assert_eq!(buf.as_slices(), (&[2][..], &[0, 1][..]));
-    /// ```
-    #[inline]
-    pub fn as_slices(&self) -> (&[T], &[T]) {
-        let contiguous = self.is_contiguous();
-        let head = self.head();
-        let tail = self.tail();
-        let buf = self.as_uninit_slice();
+    #[test]
+    fn test_partial_equal() {
+        const CAP: usize = 10;
+        let mut tester = ArrayDeque::<f64, CAP>::new();
 
-        if contiguous {
-            let (empty, buf) = buf.split_at(0);
-            unsafe {
-                (
-                    slice_assume_init_ref(&buf[tail..head]),
-                    slice_assume_init_ref(empty),
-                )
-            }
-        } else {
-            let (mid, right) = buf.split_at(tail);
-            let (left, _) = mid.split_at(head);
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                // deque starts from different tail position
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
 
-            unsafe { (slice_assume_init_ref(right), slice_assume_init_ref(left)) }
+                let mut expected = ArrayDeque::<f64, CAP>::new();
+                for x in 0..len {
+                    tester.push_back(x as f64);
+                    expected.push_back(x as f64);
+                }
+                assert_eq!(tester, expected);
+
+                // test negative
+                if len > 2 {
+                    tester.pop_front();
+                    expected.pop_back();
+                    assert!(tester != expected);
+                }
+            }
         }
     }
 
-    /// Returns a pair of slices which contain, in order, the contents of the
-    /// `ArrayDeque`.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use arraydeque::ArrayDeque;
-    ///
-    /// let mut buf: ArrayDeque<_, 7> = ArrayDeque::new();
-    ///
-    /// buf.push_back(0);
-    /// buf.push_back(1);
-    ///
-    /// assert_eq!(buf.as_mut_slices(), (&mut [0, 1][..], &mut[][..]));
-    ///
-    /// buf.push_front(2);
-    ///
-    /// assert_eq!(buf.as_mut_slices(), (&mut[2][..], &mut[0, 1][..]));
-    /// ```
-    #[inline]
-    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
-        let contiguous = self.is_contiguous();
-        let head = self.head();
-        let tail = self.tail();
-        let buf = self.as_uninit_slice_mut();
-
-        if contiguous {
-            let (empty, buf) = buf.split_at_mut(0);
-            unsafe {
-                (
-                    slice_assume_init_mut(&mut buf[tail..head]),
-                    slice_assume_init_mut(empty),
-                )
+    #[test]
+    fn test_min_max_element() {
+        // Ordered by the first field only, the second tells ties apart.
+        #[derive(Debug)]
+        struct Key(u8, u8);
+
+        impl PartialEq for Key {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
             }
-        } else {
-            let (mid, right) = buf.split_at_mut(tail);
-            let (left, _) = mid.split_at_mut(head);
+        }
 
-            unsafe { (slice_assume_init_mut(right), slice_assume_init_mut(left)) }
+        impl Eq for Key {}
+
+        impl PartialOrd for Key {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
         }
-    }
-}
 
-/// Copy of currently-unstable `MaybeUninit::slice_assume_init_ref`.
-unsafe fn slice_assume_init_ref<T>(slice: &[MaybeUninit<T>]) -> &[T] {
-    // SAFETY: casting `slice` to a `*const [T]` is safe since the caller guarantees that
-    // `slice` is initialized, and `MaybeUninit` is guaranteed to have the same layout as `T`.
-    // The pointer obtained is valid since it refers to memory owned by `slice` which is a
-    // reference and thus guaranteed to be valid for reads.
-    &*(slice as *const [MaybeUninit<T>] as *const [T])
-}
+        impl Ord for Key {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
 
-/// Copy of currently-unstable `MaybeUninit::slice_assume_init_mut`.
-unsafe fn slice_assume_init_mut<T>(slice: &mut [MaybeUninit<T>]) -> &mut [T] {
-    // SAFETY: similar to safety notes for `slice_assume_init_ref`, but we have a
-    // mutable reference which is also guaranteed to be valid for writes.
-    &mut *(slice as *mut [MaybeUninit<T>] as *mut [T])
-}
+        let mut tester: ArrayDeque<_, 4> = ArrayDeque::new();
+        assert_eq!(tester.min_element(), None);
+        assert_eq!(tester.max_element(), None);
 
-impl<T, const CAP: usize> From<ArrayDeque<T, CAP, Wrapping>> for ArrayDeque<T, CAP, Saturating> {
-    fn from(buf: ArrayDeque<T, CAP, Wrapping>) -> Self {
-        buf.into_iter().collect()
+        tester.push_back(Key(1, 0));
+        tester.push_back(Key(0, 1));
+        tester.push_front(Key(0, 2));
+        tester.push_front(Key(1, 3));
+        assert_eq!(tester.min_element().map(|k| k.1), Some(2));
+        assert_eq!(tester.max_element().map(|k| k.1), Some(0));
     }
-}
 
-impl<T, const CAP: usize> From<ArrayDeque<T, CAP, Saturating>> for ArrayDeque<T, CAP, Wrapping> {
-    fn from(buf: ArrayDeque<T, CAP, Saturating>) -> Self {
-        buf.into_iter().collect()
-    }
-}
+    #[test]
+    fn test_from_iter_bulk() {
+        const CAP: usize = 8;
+        for len in 0..CAP * 2 {
+            let tester: ArrayDeque<usize, CAP> = (0..len).collect();
+            assert!(tester.iter().copied().eq(0..cmp::min(len, CAP)));
 
-#[cfg(feature = "std")]
-impl<T, const CAP: usize, B: Behavior> From<Vec<T>> for ArrayDeque<T, CAP, B>
-where
-    Self: FromIterator<T>,
-{
-    fn from(vec: Vec<T>) -> Self {
-        vec.into_iter().collect()
-    }
-}
+            let tester: ArrayDeque<usize, CAP, Wrapping> = (0..len).collect();
+            assert!(tester.iter().copied().eq(len.saturating_sub(CAP)..len));
 
-impl<T, const CAP: usize, const N: usize, B: Behavior> From<[T; N]> for ArrayDeque<T, CAP, B>
-where
-    Self: FromIterator<T>,
-{
-    fn from(arr: [T; N]) -> Self {
-        arr.into_iter().collect()
-    }
-}
+            let tester: ArrayDeque<usize, CAP, Wrapping> = (0..len).filter(|_| true).collect();
+            assert!(tester.iter().copied().eq(len.saturating_sub(CAP)..len));
+        }
 
-#[cfg(feature = "std")]
-impl<T, const CAP: usize, B: Behavior> From<ArrayDeque<T, CAP, B>> for Vec<T>
-where
-    Self: FromIterator<T>,
-{
-    fn from(deque: ArrayDeque<T, CAP, B>) -> Self {
-        deque.into_iter().collect()
+        let mut tester: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                tester.extend_back(len..CAP * 2);
+                assert!(tester.iter().copied().eq(0..CAP));
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Drop for ArrayDeque<T, CAP, B> {
-    fn drop(&mut self) {
-        self.clear();
+    #[test]
+    fn test_compact() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                tester.compact();
+                assert_eq!(tester.tail(), 0);
+                assert!(tester.iter().copied().eq(0..len));
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Default for ArrayDeque<T, CAP, B> {
-    #[inline]
-    fn default() -> Self {
-        ArrayDeque::new()
+    #[test]
+    fn test_as_bytes() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<u8, CAP> = ArrayDeque::new();
+        for len in 0..CAP as u8 + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<u8> = (0..len).collect();
+                assert_eq!(tester.as_bytes(), &expected[..]);
+                assert_eq!(ArrayDeque::from_bytes(&expected), Ok(tester.clone()));
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> PartialEq for ArrayDeque<T, CAP, B>
-where
-    T: PartialEq,
-{
-    fn eq(&self, other: &Self) -> bool {
-        if self.len() != other.len() {
-            return false;
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_from() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<u8, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len as u8);
+                let mut reader: &[u8] = &[100; CAP];
+                let mut total = 0;
+                while let Ok(read @ 1..) = tester.read_from(&mut reader) {
+                    total += read;
+                }
+                assert_eq!(total, CAP - len);
+                assert!(tester.is_full());
+                assert!(tester.iter().take(len).copied().eq(0..len as u8));
+                assert!(tester.iter().skip(len).all(|&x| x == 100));
+            }
         }
-        let (sa, sb) = self.as_slices();
-        let (oa, ob) = other.as_slices();
-        match sa.len().cmp(&oa.len()) {
-            Ordering::Equal => sa == oa && sb == ob,
-            Ordering::Less => {
-                // Always divisible in three sections, for example:
-                // self:  [a b c|d e f]
-                // other: [0 1 2 3|4 5]
-                // front = 3, mid = 1,
-                // [a b c] == [0 1 2] && [d] == [3] && [e f] == [4 5]
-                let front = sa.len();
-                let mid = oa.len() - front;
+    }
 
-                let (oa_front, oa_mid) = oa.split_at(front);
-                let (sb_mid, sb_back) = sb.split_at(mid);
-                debug_assert_eq!(sa.len(), oa_front.len());
-                debug_assert_eq!(sb_mid.len(), oa_mid.len());
-                debug_assert_eq!(sb_back.len(), ob.len());
-                sa == oa_front && sb_mid == oa_mid && sb_back == ob
-            }
-            Ordering::Greater => {
-                let front = oa.len();
-                let mid = sa.len() - front;
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_write_to() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<u8, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len as u8);
+                let mut out = Vec::new();
+                let mut calls = 0;
+                while tester.write_to(&mut out).unwrap() > 0 {
+                    calls += 1;
+                }
+                assert!(calls <= 2);
+                assert!(tester.is_empty());
+                assert!(out.iter().copied().eq(0..len as u8));
+            }
+        }
+    }
 
-                let (sa_front, sa_mid) = sa.split_at(front);
-                let (ob_mid, ob_back) = ob.split_at(mid);
-                debug_assert_eq!(sa_front.len(), oa.len());
-                debug_assert_eq!(sa_mid.len(), ob_mid.len());
-                debug_assert_eq!(sb.len(), ob_back.len());
-                sa_front == oa && sa_mid == ob_mid && sb == ob_back
+    #[test]
+    fn test_put_get_bytes() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<u8, CAP, Wrapping> = ArrayDeque::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
             }
+            tester.put_u8(0xff).unwrap();
+            tester.put_u16_be(0x1234).unwrap();
+            tester.put_u16_le(0x5678).unwrap();
+            tester.put_slice(&[1, 2]).unwrap();
+            assert!(tester.put_slice(&[3, 4]).is_err());
+            assert_eq!(tester.len(), 7);
+
+            assert_eq!(tester.get_u8(), Some(0xff));
+            assert_eq!(tester.get_u16_be(), Some(0x1234));
+            assert_eq!(tester.get_u16_le(), Some(0x5678));
+            assert_eq!(tester.get_u16_be(), Some(0x0102));
+            assert_eq!(tester.get_u16_le(), None);
+            assert!(tester.is_empty());
         }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Eq for ArrayDeque<T, CAP, B> where T: Eq {}
+    #[test]
+    fn test_into_behavior() {
+        use std::rc::Rc;
 
-impl<T, const CAP: usize, B: Behavior> PartialOrd for ArrayDeque<T, CAP, B>
-where
-    T: PartialOrd,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.iter().partial_cmp(other.iter())
+        const CAP: usize = 4;
+        let elem = Rc::new(());
+        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
+        unsafe {
+            tester.set_tail(3);
+        }
+        tester.extend_back((0..3).map(|i| (i, elem.clone())));
+
+        let mut tester: ArrayDeque<_, CAP, Wrapping> = tester.into();
+        assert_eq!(tester.tail(), 3);
+        assert!(tester.push_back((3, elem.clone())).is_none());
+        assert_eq!(tester.push_back((4, elem.clone())).unwrap().0, 0);
+        assert!(tester.iter().map(|x| x.0).eq(1..5));
+        assert_eq!(Rc::strong_count(&elem), 5);
+
+        let tester: ArrayDeque<_, CAP> = tester.into_behavior();
+        assert!(tester.iter().map(|x| x.0).eq(1..5));
+        drop(tester);
+        assert_eq!(Rc::strong_count(&elem), 1);
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Ord for ArrayDeque<T, CAP, B>
-where
-    T: Ord,
-{
-    #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.iter().cmp(other.iter())
+    #[test]
+    fn test_resize_front() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<usize, CAP, Wrapping> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for new_len in 0..CAP + 2 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    tester.resize_front(new_len, 100);
+                    let new_len = cmp::min(new_len, CAP);
+                    assert_eq!(tester.len(), new_len);
+                    let grown = new_len.saturating_sub(len);
+                    assert!(tester.iter().take(grown).all(|&x| x == 100));
+                    assert!(tester
+                        .iter()
+                        .skip(grown)
+                        .copied()
+                        .eq(len.saturating_sub(new_len)..len));
+                }
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Hash for ArrayDeque<T, CAP, B>
-where
-    T: Hash,
-{
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        let (a, b) = self.as_slices();
-        Hash::hash_slice(a, state);
-        Hash::hash_slice(b, state);
+    #[test]
+    fn test_contents_eq() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        let mut other = ArrayDeque::<usize, 10, Wrapping>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                for other_padding in 0..10 {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                        other.set_len(0);
+                        other.set_tail(other_padding);
+                    }
+                    tester.extend_back(0..len);
+                    other.extend_back(0..len);
+                    assert!(tester.contents_eq(&other));
+                    assert!(other.contents_eq(&tester));
+
+                    other.push_back(len);
+                    assert!(!tester.contents_eq(&other));
+                    if len > 0 {
+                        other.pop_front();
+                        assert!(!tester.contents_eq(&other));
+                    }
+                }
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> Index<usize> for ArrayDeque<T, CAP, B> {
-    type Output = T;
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hash_matches_slice() {
+        use std::collections::hash_map::DefaultHasher;
 
-    #[inline]
-    fn index(&self, index: usize) -> &T {
-        let len = self.len();
-        self.get(index)
-            .or_else(|| {
-                panic!(
-                    "index out of bounds: the len is {} but the index is {}",
-                    len, index
-                )
-            })
-            .unwrap()
-    }
-}
+        fn hash<H: Hash + ?Sized>(value: &H) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
 
-impl<T, const CAP: usize, B: Behavior> IndexMut<usize> for ArrayDeque<T, CAP, B> {
-    #[inline]
-    fn index_mut(&mut self, index: usize) -> &mut T {
-        let len = self.len();
-        self.get_mut(index)
-            .or_else(|| {
-                panic!(
-                    "index out of bounds: the len is {} but the index is {}",
-                    len, index
-                )
-            })
-            .unwrap()
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<u8, CAP>::new();
+        let mut strings = ArrayDeque::<String, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                strings.clear();
+                unsafe {
+                    strings.set_tail(padding);
+                }
+                tester.extend_back(0..len as u8);
+                strings.extend_back((0..len).map(|x| x.to_string()));
+
+                let expected: Vec<u8> = (0..len as u8).collect();
+                assert_eq!(hash(&tester), hash(&expected[..]));
+                assert_eq!(hash(&tester), hash(&expected));
+
+                let expected: Vec<String> = (0..len).map(|x| x.to_string()).collect();
+                assert_eq!(hash(&strings), hash(&expected[..]));
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> IntoIterator for ArrayDeque<T, CAP, B> {
-    type Item = T;
-    type IntoIter = IntoIter<T, CAP, B>;
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_hash_layout_independent() {
+        // Records every call, so any difference in how the input is split
+        // between calls shows up
+        #[derive(Default, PartialEq, Debug)]
+        struct WriteLog(Vec<Vec<u8>>);
+
+        impl Hasher for WriteLog {
+            fn finish(&self) -> u64 {
+                0
+            }
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter { inner: self }
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.push(bytes.to_vec());
+            }
+        }
+
+        fn log<H: Hash>(value: &H) -> WriteLog {
+            let mut hasher = WriteLog::default();
+            value.hash(&mut hasher);
+            hasher
+        }
+
+        const CAP: usize = 8;
+        let contiguous: ArrayDeque<u8, CAP> = (0..6).collect();
+        for padding in 0..CAP {
+            let mut tester = ArrayDeque::<u8, CAP>::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..6);
+            assert_eq!(tester, contiguous);
+            assert_eq!(log(&tester), log(&contiguous));
+        }
     }
-}
 
-impl<'a, T, const CAP: usize, B: Behavior> IntoIterator for &'a ArrayDeque<T, CAP, B> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    #[test]
+    fn test_extend_wrapping() {
+        const CAP: usize = 4;
+        let mut tester: ArrayDeque<usize, CAP, Wrapping> = ArrayDeque::new();
+        let mut expected: ArrayDeque<usize, CAP, Wrapping> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for count in 0..CAP * 2 {
+                tester.clear();
+                expected.clear();
+                tester.extend_back(0..len);
+                expected.extend_back(0..len);
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+                tester.extend(100..100 + count);
+                expected.extend_back(100..100 + count);
+                assert_eq!(tester, expected);
+            }
+        }
+
+        let mut tester: ArrayDeque<_, CAP, Wrapping> = ArrayDeque::new();
+        tester.extend(0..6);
+        assert_eq!(tester, [2, 3, 4, 5].into());
     }
-}
 
-impl<'a, T, const CAP: usize, B: Behavior> IntoIterator for &'a mut ArrayDeque<T, CAP, B> {
-    type Item = &'a mut T;
-    type IntoIter = IterMut<'a, T>;
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_iter() {
+        use rayon::prelude::*;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let doubled: Vec<_> = tester.par_iter().map(|x| x * 2).collect();
+                assert!(doubled.into_iter().eq((0..len).map(|x| x * 2)));
+
+                tester.par_iter_mut().for_each(|x| *x += 1);
+                assert!(tester.iter().copied().eq(1..len + 1));
+            }
+        }
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> fmt::Debug for ArrayDeque<T, CAP, B>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_list().entries(self).finish()
+    #[test]
+    fn test_get_pair_mut() {
+        const CAP: usize = 5;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..CAP);
+            for i in 0..CAP + 1 {
+                for j in 0..CAP + 1 {
+                    match tester.get_pair_mut(i, j) {
+                        Some((a, b)) => {
+                            assert!(i != j && i < CAP && j < CAP);
+                            assert_eq!((*a, *b), (i, j));
+                            mem::swap(a, b);
+                            assert_eq!((tester[i], tester[j]), (j, i));
+                            tester.swap(i, j);
+                        }
+                        None => assert!(i == j || i >= CAP || j >= CAP),
+                    }
+                }
+            }
+        }
     }
-}
 
-#[inline]
-fn wrap_add(index: usize, addend: usize, capacity: usize) -> usize {
-    debug_assert!(addend <= capacity);
-    (index + addend) % capacity
-}
+    #[test]
+    fn test_rotate_to() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for target in 0..len + 1 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let found = tester.rotate_to(&target);
+                    assert_eq!(found, target < len);
+                    let start = if found { target } else { 0 };
+                    assert!(tester.iter().copied().eq((start..len).chain(0..start)));
+                }
+            }
+        }
+    }
 
-#[inline]
-fn wrap_sub(index: usize, subtrahend: usize, capacity: usize) -> usize {
-    debug_assert!(subtrahend <= capacity);
-    (index + capacity - subtrahend) % capacity
-}
+    #[test]
+    fn test_reset_to() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        let mut wrapping = ArrayDeque::<usize, CAP, Wrapping>::new();
+        for len in 0..CAP + 1 {
+            for new_len in 0..CAP * 2 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                        wrapping.set_len(0);
+                        wrapping.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    wrapping.extend_back(0..len);
+
+                    tester.reset_to(100..100 + new_len);
+                    assert_eq!(tester.tail(), 0);
+                    assert!(tester
+                        .iter()
+                        .copied()
+                        .eq((100..).take(cmp::min(new_len, CAP))));
+
+                    wrapping.reset_to(100..100 + new_len);
+                    assert_eq!(wrapping.tail(), 0);
+                    assert!(wrapping
+                        .iter()
+                        .copied()
+                        .eq((100 + new_len.saturating_sub(CAP))..100 + new_len));
+                }
+            }
+        }
+    }
 
-/// `ArrayDeque` iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct Iter<'a, T: 'a> {
-    ring: &'a [MaybeUninit<T>],
-    tail: usize,
-    len: usize,
-}
+    #[test]
+    fn test_replace_contents() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for new_len in 0..CAP * 2 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let src: Vec<_> = (100..100 + new_len).collect();
+                    tester.replace_contents(&src);
+                    assert_eq!(tester.tail(), 0);
+                    assert!(tester.iter().eq(src.iter().take(CAP)));
+                }
+            }
+        }
+    }
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+    #[test]
+    fn test_as_chunks() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let (chunks, remainder) = tester.as_chunks::<3>();
+                assert_eq!(chunks.len(), len / 3);
+                assert!(chunks.iter().flatten().chain(remainder).copied().eq(0..len));
+            }
+        }
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<&'a T> {
-        if self.len == 0 {
-            return None;
+    #[test]
+    fn test_chunks_mut() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for chunk_size in 1..CAP + 2 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let count = match len % chunk_size {
+                        0 => len / chunk_size,
+                        _ => len / chunk_size + 1,
+                    };
+
+                    let chunks = tester.chunks_mut(chunk_size);
+                    assert_eq!(chunks.len(), count);
+                    let mut seen = 0;
+                    for (i, (a, b)) in chunks.enumerate() {
+                        assert_eq!(
+                            a.len() + b.len(),
+                            cmp::min(chunk_size, len - i * chunk_size)
+                        );
+                        assert!(!a.is_empty());
+                        for x in a.iter_mut().chain(b) {
+                            assert_eq!(*x, seen);
+                            seen += 1;
+                            *x += 100;
+                        }
+                    }
+                    assert_eq!(seen, len);
+
+                    let chunks = tester.rchunks_mut(chunk_size);
+                    assert_eq!(chunks.len(), count);
+                    let mut seen = len;
+                    for (i, (a, b)) in chunks.enumerate() {
+                        assert_eq!(
+                            a.len() + b.len(),
+                            cmp::min(chunk_size, len - i * chunk_size)
+                        );
+                        assert!(!a.is_empty());
+                        for x in a.iter_mut().chain(b).rev() {
+                            seen -= 1;
+                            assert_eq!(*x, seen + 100);
+                            *x -= 100;
+                        }
+                    }
+                    assert_eq!(seen, 0);
+                    assert!(tester.iter().copied().eq(0..len));
+                }
+            }
         }
-        let tail = self.tail;
-        self.tail = wrap_add(self.tail, 1, self.ring.len());
-        self.len -= 1;
-        unsafe { Some(self.ring.get_unchecked(tail).assume_init_ref()) }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    #[test]
+    fn test_extend_ref() {
+        let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+        tester.extend(&[0, 1]);
+        tester.extend([2, 3, 4].iter());
+        assert_eq!(tester, [0, 1, 2, 3].into());
+
+        let mut tester: ArrayDeque<usize, 4, Wrapping> = ArrayDeque::new();
+        tester.extend(&[0, 1]);
+        tester.extend([2, 3, 4].iter());
+        assert_eq!(tester, [1, 2, 3, 4].into());
     }
-}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<&'a T> {
-        if self.len == 0 {
-            return None;
-        }
-        self.len -= 1;
-        let head = wrap_add(self.tail, self.len, self.ring.len());
-        unsafe { Some(self.ring.get_unchecked(head).assume_init_ref()) }
-    }
-}
+    #[test]
+    fn test_from_iter_ref() {
+        let src = [0, 1, 2, 3, 4];
+        let tester: ArrayDeque<usize, 4> = src.iter().collect();
+        assert_eq!(tester, [0, 1, 2, 3].into());
 
-impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+        let tester: ArrayDeque<usize, 4, Wrapping> = src.iter().collect();
+        assert_eq!(tester, [1, 2, 3, 4].into());
+    }
 
-impl<'a, T> Clone for Iter<'a, T> {
-    fn clone(&self) -> Self {
-        Iter {
-            ring: self.ring,
-            tail: self.tail,
-            len: self.len,
+    #[test]
+    fn test_iter_rfold() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).rev().collect();
+
+                let mut iter = tester.iter();
+                let skipped = iter.next().is_some() as usize;
+                let folded = iter.rfold(Vec::new(), |mut acc, &x| {
+                    acc.push(x);
+                    acc
+                });
+                assert_eq!(folded, &expected[..len - skipped]);
+
+                let folded = tester.iter_mut().rev().fold(Vec::new(), |mut acc, x| {
+                    *x += 1;
+                    acc.push(*x - 1);
+                    acc
+                });
+                assert_eq!(folded, expected);
+                assert!(tester.iter().copied().eq(1..len + 1));
+            }
         }
     }
-}
-
-/// `ArrayDeque` mutable iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct IterMut<'a, T: 'a> {
-    ring: &'a mut [MaybeUninit<T>],
-    tail: usize,
-    len: usize,
-}
 
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
-
-    #[inline]
-    fn next(&mut self) -> Option<&'a mut T> {
-        if self.len == 0 {
-            return None;
-        }
-        let tail = self.tail;
-        self.tail = wrap_add(self.tail, 1, self.ring.len());
-        self.len -= 1;
-        unsafe {
-            let elem = self.ring.get_unchecked_mut(tail).assume_init_mut();
-            Some(std::mem::transmute::<&mut T, &'a mut T>(elem))
+    #[test]
+    fn test_iter_short_circuit() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<usize, CAP>::new();
+        for len in 0..CAP + 1 {
+            for target in 0..len + 1 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+
+                    let mut iter = tester.iter();
+                    assert_eq!(
+                        iter.find(|&&x| x == target),
+                        (0..len).find(|&x| x == target).as_ref()
+                    );
+                    assert!(iter.copied().eq(cmp::min(target + 1, len)..len));
+
+                    let mut iter = tester.iter();
+                    assert_eq!(iter.any(|&x| x == target), target < len);
+                    assert!(iter.copied().eq(cmp::min(target + 1, len)..len));
+
+                    let mut iter = tester.iter();
+                    assert_eq!(iter.all(|&x| x != target), target == len);
+                    assert!(iter.copied().eq(cmp::min(target + 1, len)..len));
+                }
+            }
         }
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
-    }
-}
+    #[test]
+    fn test_partial_equal_split() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<u32, CAP>::new();
+        let mut other = ArrayDeque::<u32, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                for other_padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                        other.set_len(0);
+                        other.set_tail(other_padding);
+                    }
+                    tester.extend_back(0..len as u32);
+                    other.extend_back(0..len as u32);
+                    assert_eq!(tester, other);
 
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<&'a mut T> {
-        if self.len == 0 {
-            return None;
-        }
-        self.len -= 1;
-        let head = wrap_add(self.tail, self.len, self.ring.len());
-        unsafe {
-            let elem = self.ring.get_unchecked_mut(head).assume_init_mut();
-            Some(std::mem::transmute::<&mut T, &'a mut T>(elem))
+                    if let Some(back) = other.back_mut() {
+                        *back += 1;
+                        assert!(tester != other);
+                    }
+                }
+            }
         }
     }
-}
 
-impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+    #[test]
+    fn test_retain_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
 
-/// By-value `ArrayDeque` iterator
-#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct IntoIter<T, const CAP: usize, B: Behavior> {
-    inner: ArrayDeque<T, CAP, B>,
-}
+        const CAP: usize = 8;
+        let elem = Rc::new(());
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            for panic_at in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back((0..CAP).map(|i| (i, elem.clone())));
 
-impl<T, const CAP: usize, B: Behavior> Iterator for IntoIter<T, CAP, B> {
-    type Item = T;
+                let result = catch_unwind(AssertUnwindSafe(|| {
+                    tester.retain(|&(i, _)| {
+                        if i == panic_at {
+                            panic!();
+                        }
+                        i % 2 == 0
+                    })
+                }));
+                assert!(result.is_err());
+
+                // Checked elements are filtered, the rest are kept
+                let expected = (0..panic_at).filter(|i| i % 2 == 0).chain(panic_at..CAP);
+                assert!(tester.iter().map(|x| x.0).eq(expected));
+                assert_eq!(Rc::strong_count(&elem), tester.len() + 1);
+                tester.clear();
+                assert_eq!(Rc::strong_count(&elem), 1);
+            }
+        }
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.inner.pop_front()
-    }
+        // Rejected elements are dropped before the next one is checked
+        tester.extend_back((0..CAP).map(|i| (i, elem.clone())));
+        let mut rejected = 0;
+        tester.retain(|&(i, _)| {
+            assert_eq!(Rc::strong_count(&elem), CAP + 1 - rejected);
+            rejected += i % 2;
+            i % 2 == 0
+        });
+        assert_eq!(Rc::strong_count(&elem), CAP / 2 + 1);
+        tester.clear();
+
+        // A panicking destructor leaves the deque consistent
+        struct PanicOnDrop(usize, #[allow(dead_code)] Rc<()>);
+
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                if self.0 == 3 && !std::thread::panicking() {
+                    panic!();
+                }
+            }
+        }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.inner.len();
-        (len, Some(len))
-    }
-}
+        for padding in 0..CAP {
+            let mut tester = ArrayDeque::<_, CAP>::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back((0..CAP).map(|i| PanicOnDrop(i, elem.clone())));
+            let result = catch_unwind(AssertUnwindSafe(|| tester.retain(|x| x.0 % 2 == 0)));
+            assert!(result.is_err());
 
-impl<T, const CAP: usize, B: Behavior> DoubleEndedIterator for IntoIter<T, CAP, B> {
-    #[inline]
-    fn next_back(&mut self) -> Option<T> {
-        self.inner.pop_back()
+            let expected = [0, 2].iter().copied().chain(4..CAP);
+            assert!(tester.iter().map(|x| x.0).eq(expected));
+            assert_eq!(Rc::strong_count(&elem), tester.len() + 1);
+        }
+        assert_eq!(Rc::strong_count(&elem), 1);
     }
-}
 
-impl<T, const CAP: usize, B: Behavior> ExactSizeIterator for IntoIter<T, CAP, B> {}
-
-/// Draining `ArrayDeque` iterator
-pub struct Drain<'a, T, const CAP: usize, B>
-where
-    B: Behavior,
-{
-    after_tail: usize,
-    after_len: usize,
-    iter: Iter<'a, T>,
-    deque: *mut ArrayDeque<T, CAP, B>,
-}
+    #[test]
+    fn test_drain_forget() {
+        use std::rc::Rc;
 
-impl<'a, T, const CAP: usize, B> Drop for Drain<'a, T, CAP, B>
-where
-    B: Behavior,
-{
-    fn drop(&mut self) {
-        for _ in self.by_ref() {}
+        const CAP: usize = 8;
+        let elem = Rc::new(());
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            for start in 0..CAP + 1 {
+                for end in start..CAP + 1 {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back((0..CAP).map(|i| (i, elem.clone())));
+                    let before = Rc::strong_count(&elem);
+
+                    let mut drain = tester.drain(start..end);
+                    drop(drain.next());
+                    mem::forget(drain);
+
+                    assert_eq!(tester.len(), start);
+                    assert!(tester.iter().map(|x| x.0).eq(0..start));
+                    let yielded = (start < end) as usize;
+                    assert_eq!(Rc::strong_count(&elem), before - yielded);
+
+                    // Usable again after the leak
+                    tester.extend_back((0..CAP - start).map(|i| (i, elem.clone())));
+                    assert!(tester.is_full());
+                    tester.clear();
+                }
+            }
+        }
+    }
 
-        let source_deque = unsafe { &mut *self.deque };
+    #[test]
+    fn test_insert_remove_model() {
+        use std::rc::Rc;
 
-        let tail_len = source_deque.len();
-        let head_len = self.after_len;
+        const CAP: usize = 9;
+        let elem = Rc::new(());
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP {
+            for index in 0..len + 1 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back((0..len).map(|i| (i, elem.clone())));
+                    let mut expected: Vec<_> = (0..len).collect();
 
-        let orig_tail = source_deque.tail();
-        let drain_tail = wrap_add(orig_tail, tail_len, CAP);
-        let drain_head = self.after_tail;
-        let orig_head = wrap_add(drain_head, head_len, CAP);
-        let orig_len = wrap_sub(orig_head, orig_tail, CAP);
+                    assert!(tester.insert(index, (100, elem.clone())).is_ok());
+                    expected.insert(index, 100);
+                    assert!(tester.iter().map(|x| x.0).eq(expected.iter().copied()));
 
-        // Restore the original len value
-        unsafe { source_deque.set_len(orig_len) }
-        match (tail_len, head_len) {
-            (0, 0) => unsafe {
-                source_deque.set_tail(0);
-                source_deque.set_len(0);
-            },
-            (0, _) => unsafe {
-                source_deque.set_tail(drain_head);
-                source_deque.set_len(head_len);
-            },
-            (_, 0) => unsafe { source_deque.set_len(tail_len) },
-            _ => unsafe {
-                if tail_len <= head_len {
-                    let new_tail = wrap_sub(drain_head, tail_len, CAP);
-                    source_deque.set_tail(new_tail);
-                    source_deque.set_len(tail_len + head_len);
-                    source_deque.wrap_copy(new_tail, orig_tail, tail_len);
-                } else {
-                    source_deque.set_len(tail_len + head_len);
-                    source_deque.wrap_copy(drain_tail, drain_head, head_len);
+                    for remove in [index, 0, len] {
+                        if remove < tester.len() {
+                            assert_eq!(tester.remove(remove).unwrap().0, expected.remove(remove));
+                            assert!(tester.iter().map(|x| x.0).eq(expected.iter().copied()));
+                        }
+                    }
+                    assert_eq!(Rc::strong_count(&elem), tester.len() + 1);
+                    tester.clear();
                 }
-            },
+            }
         }
     }
-}
-
-impl<'a, T, const CAP: usize, B: Behavior> Iterator for Drain<'a, T, CAP, B> {
-    type Item = T;
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.iter.next().map(|elt| unsafe { ptr::read(elt) })
-    }
+    #[test]
+    fn test_extend_back_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        const CAP: usize = 8;
+        let elem = Rc::new(());
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for panic_at in 0..CAP {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back((0..len).map(|i| (i, elem.clone())));
+                    let result = catch_unwind(AssertUnwindSafe(|| {
+                        tester.extend_back((len..CAP).map(|i| {
+                            if i == panic_at {
+                                panic!();
+                            }
+                            (i, elem.clone())
+                        }));
+                    }));
+                    assert_eq!(result.is_err(), panic_at >= len);
+                    let expected_len = if panic_at >= len { panic_at } else { CAP };
+                    assert!(tester.iter().map(|x| x.0).eq(0..expected_len));
+                    assert_eq!(Rc::strong_count(&elem), expected_len + 1);
+                    tester.clear();
+                }
+            }
+        }
     }
-}
 
-impl<'a, T, const CAP: usize, B: Behavior> DoubleEndedIterator for Drain<'a, T, CAP, B> {
-    #[inline]
-    fn next_back(&mut self) -> Option<T> {
-        self.iter.next_back().map(|elt| unsafe { ptr::read(elt) })
-    }
-}
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_bytes() {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_ser_tokens, Token};
 
-impl<'a, T, const CAP: usize, B: Behavior> ExactSizeIterator for Drain<'a, T, CAP, B> {}
+        #[derive(Debug, PartialEq)]
+        struct Bytes(ArrayDeque<u8, 4>);
 
-#[cfg(test)]
-mod tests {
-    #![allow(unused_must_use)]
-    use super::*;
+        impl Serialize for Bytes {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                crate::serde_bytes::serialize(&self.0, serializer)
+            }
+        }
 
-    #[test]
-    fn test_simple() {
-        macro_rules! test {
-            ($behavior:ident) => {{
-                let mut tester: ArrayDeque<_, 7, $behavior> = ArrayDeque::new();
-                assert_eq!(tester.capacity(), 7);
-                assert_eq!(tester.len(), 0);
+        impl<'de> Deserialize<'de> for Bytes {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                crate::serde_bytes::deserialize(deserializer).map(Bytes)
+            }
+        }
 
-                tester.push_back(1);
-                tester.push_back(2);
-                tester.push_back(3);
-                tester.push_back(4);
-                assert_eq!(tester.len(), 4);
-                assert_eq!(tester.pop_front(), Some(1));
-                assert_eq!(tester.pop_front(), Some(2));
-                assert_eq!(tester.len(), 2);
-                assert_eq!(tester.pop_front(), Some(3));
-                assert_eq!(tester.pop_front(), Some(4));
-                assert_eq!(tester.pop_front(), None);
-            }};
+        for padding in 0..4 {
+            let mut tester = ArrayDeque::<u8, 4>::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back([1, 2, 3]);
+            let tester = Bytes(tester);
+            assert_ser_tokens(&tester, &[Token::Bytes(&[1, 2, 3])]);
+            assert_de_tokens(&tester, &[Token::Bytes(&[1, 2, 3])]);
+            assert_de_tokens(
+                &tester,
+                &[
+                    Token::Seq { len: Some(3) },
+                    Token::U8(1),
+                    Token::U8(2),
+                    Token::U8(3),
+                    Token::SeqEnd,
+                ],
+            );
         }
 
-        test!(Saturating);
-        test!(Wrapping);
+        assert_de_tokens_error::<Bytes>(
+            &[Token::Bytes(&[1, 2, 3, 4, 5])],
+            "invalid length 5, expected at most 4 bytes",
+        );
     }
 
     #[test]
-    fn test_simple_reversely() {
-        macro_rules! test {
-            ($behavior:ident) => {{
-                let mut tester: ArrayDeque<_, 7, $behavior> = ArrayDeque::new();
-                assert_eq!(tester.capacity(), 7);
-                assert_eq!(tester.len(), 0);
+    fn test_display_joined() {
+        let tester: ArrayDeque<i32, 4> = ArrayDeque::new();
+        assert_eq!(tester.display_joined(", ").to_string(), "");
 
-                tester.push_front(1);
-                tester.push_front(2);
-                tester.push_front(3);
-                tester.push_front(4);
-                assert_eq!(tester.len(), 4);
-                assert_eq!(tester.pop_back(), Some(1));
-                assert_eq!(tester.pop_back(), Some(2));
-                assert_eq!(tester.len(), 2);
-                assert_eq!(tester.pop_back(), Some(3));
-                assert_eq!(tester.pop_back(), Some(4));
-                assert_eq!(tester.pop_back(), None);
-            }};
+        for padding in 0..4 {
+            let mut tester: ArrayDeque<i32, 4> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.push_back(1).unwrap();
+            assert_eq!(tester.display_joined(", ").to_string(), "1");
+            tester.extend_back([2, 3, 4]);
+            assert_eq!(tester.display_joined(", ").to_string(), "1, 2, 3, 4");
+            assert_eq!(format!("{:>2}", tester.display_joined("")), " 1 2 3 4");
         }
+    }
 
-        test!(Saturating);
-        test!(Wrapping);
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_vec_from_ref() {
+        for padding in 0..4 {
+            let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..4);
+            let vec = Vec::from(&tester);
+            assert_eq!(vec, [0, 1, 2, 3]);
+            assert_eq!(vec.capacity(), 4);
+            assert!(tester.iter().copied().eq(0..4));
+        }
     }
 
     #[test]
-    fn test_overflow_saturating() {
-        let mut tester: ArrayDeque<_, 2, Saturating> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), Ok(()));
-        assert_eq!(tester.push_back(2), Ok(()));
-        assert_eq!(tester.push_back(3), Err(CapacityError { element: 3 }));
+    fn test_get_wrapping() {
+        for padding in 0..4 {
+            let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            assert_eq!(tester.get_wrapping(0), None);
+            tester.extend_back(0..3);
+            for i in 0..10 {
+                assert_eq!(tester.get_wrapping(i), Some(&(i % 3)));
+            }
+            assert_eq!(tester.get_wrapping(usize::MAX), Some(&(usize::MAX % 3)));
+        }
+    }
 
-        let mut tester: ArrayDeque<_, 2, Saturating> = ArrayDeque::new();
-        assert_eq!(tester.insert(0, 1), Ok(()));
-        assert_eq!(tester.insert(1, 2), Ok(()));
-        assert_eq!(tester.insert(2, 3), Err(CapacityError { element: 3 }));
+    #[test]
+    fn test_retain_indexed() {
+        for padding in 0..5 {
+            let mut tester: ArrayDeque<usize, 5> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back(10..15);
+            let mut seen = [0; 5];
+            let removed = tester.retain_indexed(|i, &x| {
+                seen[i] = x;
+                i == 0 || x % 3 == 0
+            });
+            assert_eq!(seen, [10, 11, 12, 13, 14]);
+            assert_eq!(removed, 3);
+            assert!(tester.iter().copied().eq([10, 12]));
+        }
     }
 
     #[test]
-    fn test_overflow_wrapping() {
-        let mut tester: ArrayDeque<_, 2, Wrapping> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), None);
-        assert_eq!(tester.push_back(2), None);
-        assert_eq!(tester.push_back(3), Some(1));
+    fn test_checked_insert() {
+        for padding in 0..4 {
+            let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            assert_eq!(
+                tester.checked_insert(1, 9),
+                Err(InsertError::OutOfBounds(9))
+            );
+            tester.checked_insert(0, 1).unwrap();
+            tester.checked_insert(0, 0).unwrap();
+            tester.checked_insert(2, 3).unwrap();
+            tester.checked_insert(2, 2).unwrap();
+            assert!(tester.iter().copied().eq(0..4));
+            assert_eq!(
+                tester.checked_insert(5, 9),
+                Err(InsertError::OutOfBounds(9))
+            );
+            assert_eq!(tester.checked_insert(4, 9), Err(InsertError::Full(9)));
+            assert_eq!(InsertError::Full(9).into_element(), 9);
+            assert!(tester.iter().copied().eq(0..4));
+        }
     }
 
     #[test]
-    fn test_pop_empty() {
-        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
-        assert_eq!(tester.push_back(1), Ok(()));
-        assert_eq!(tester.pop_front(), Some(1));
-        assert_eq!(tester.is_empty(), true);
-        assert_eq!(tester.len(), 0);
-        assert_eq!(tester.pop_front(), None);
+    fn test_range_argument() {
+        use std::ops::Bound::{Excluded, Included, Unbounded};
+
+        fn bounds<R: RangeArgument>(range: R, len: usize) -> (usize, usize) {
+            (range.start().unwrap_or(0), range.end().unwrap_or(len))
+        }
+
+        const LEN: usize = 6;
+        assert_eq!(bounds(.., LEN), (0, LEN));
+        assert_eq!(bounds((Unbounded, Unbounded), LEN), (0, LEN));
+        for start in 0..=LEN {
+            assert_eq!(bounds(start.., LEN), (start, LEN));
+            assert_eq!(bounds((Included(start), Unbounded), LEN), (start, LEN));
+            assert_eq!(bounds(..start, LEN), (0, start));
+            assert_eq!(bounds((Unbounded, Excluded(start)), LEN), (0, start));
+            for end in start..=LEN {
+                let expected = (start, end);
+                assert_eq!(bounds(start..end, LEN), expected);
+                assert_eq!(bounds((Included(start), Excluded(end)), LEN), expected);
+                if end > 0 {
+                    assert_eq!(bounds(start..=end - 1, LEN), expected);
+                    assert_eq!(bounds((Included(start), Included(end - 1)), LEN), expected);
+                }
+                if start > 0 {
+                    assert_eq!(bounds((Excluded(start - 1), Excluded(end)), LEN), expected);
+                }
+                if start == 0 && end > 0 {
+                    assert_eq!(bounds(..=end - 1, LEN), expected);
+                }
+            }
+        }
+        assert_eq!(bounds(..=usize::MAX, LEN), (0, usize::MAX));
+        assert_eq!(
+            bounds((Excluded(usize::MAX), Unbounded), LEN),
+            (usize::MAX, LEN)
+        );
+
+        let mut tester: ArrayDeque<_, 4> = [0, 1, 2, 3].into();
+        assert!(tester.drain((Excluded(0), Included(2))).eq([1, 2]));
+        assert_eq!(tester, [0, 3].into());
     }
 
     #[test]
-    fn test_index() {
-        let mut tester: ArrayDeque<_, 3> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        tester.push_back(3);
-        assert_eq!(tester[0], 1);
-        // pop_front 1 <- [2, 3]
-        assert_eq!(tester.pop_front(), Some(1));
-        assert_eq!(tester[0], 2);
-        assert_eq!(tester.len(), 2);
-        // push_front 0 -> [0, 2, 3]
-        tester.push_front(0);
-        assert_eq!(tester[0], 0);
-        // [0, 2] -> 3 pop_back
-        assert_eq!(tester.pop_back(), Some(3));
-        assert_eq!(tester[1], 2);
+    fn test_split_at() {
+        for padding in 0..5 {
+            let mut tester: ArrayDeque<usize, 5> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..5);
+            for mid in 0..=5 {
+                let (front, back) = tester.split_at(mid);
+                assert_eq!(front.len(), mid);
+                assert!(front.copied().eq(0..mid));
+                assert!(back.rev().copied().eq((mid..5).rev()));
+            }
+        }
     }
 
     #[test]
     #[should_panic]
-    fn test_index_overflow() {
-        let mut tester: ArrayDeque<_, 3> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        tester[2];
+    fn test_split_at_out_of_bounds() {
+        let tester: ArrayDeque<usize, 5> = [0, 1].into();
+        let _ = tester.split_at(3);
     }
 
     #[test]
-    fn test_iter() {
-        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        {
-            let mut iter = tester.iter();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(&1));
-            assert_eq!(iter.next(), Some(&2));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
+    fn test_as_slices_from() {
+        for padding in 0..5 {
+            let mut tester: ArrayDeque<usize, 5> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back(0..5);
+            for start in 0..=5 {
+                let (a, b) = tester.as_slices_from(start);
+                assert_eq!(a.is_empty(), start == 5);
+                assert!(a.iter().chain(b).copied().eq(start..5));
+            }
         }
-        tester.pop_front();
-        tester.push_back(3);
-        {
-            let mut iter = (&tester).into_iter();
-            assert_eq!(iter.next(), Some(&2));
+    }
 
-            // test clone
-            let mut iter2 = iter.clone();
-            assert_eq!(iter.next(), Some(&3));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter2.next(), Some(&3));
-            assert_eq!(iter2.next(), None);
+    #[test]
+    fn test_concat_into() {
+        for padding in 0..5 {
+            for dst_padding in 0..5 {
+                for dst_len in 0..=5 {
+                    let mut src: ArrayDeque<usize, 5> = ArrayDeque::new();
+                    let mut dst: ArrayDeque<usize, 5> = ArrayDeque::new();
+                    unsafe {
+                        src.set_tail(padding);
+                        dst.set_tail(dst_padding);
+                    }
+                    src.extend_back(10..13);
+                    dst.extend_back(0..dst_len);
+                    let wrapping_src = src.clone().into_behavior::<Wrapping>();
+                    let mut wrapping_dst = dst.clone().into_behavior::<Wrapping>();
+
+                    src.concat_into(&mut dst);
+                    let expected = (0..dst_len).chain(10..13);
+                    assert!(dst.iter().copied().eq(expected.clone().take(5)));
+
+                    wrapping_src.concat_into(&mut wrapping_dst);
+                    let skip = (dst_len + 3).saturating_sub(5);
+                    assert!(wrapping_dst.iter().copied().eq(expected.skip(skip)));
+                }
+            }
         }
     }
 
     #[test]
-    fn test_iter_mut() {
-        let mut tester: ArrayDeque<_, 2> = ArrayDeque::new();
-        tester.push_back(1);
-        tester.push_back(2);
-        {
-            let mut iter = tester.iter_mut();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(&mut 1));
-            assert_eq!(iter.next(), Some(&mut 2));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
-        }
-        tester.pop_front();
-        tester.push_back(3);
-        {
-            let mut iter = (&mut tester).into_iter();
-            assert_eq!(iter.next(), Some(&mut 2));
-            assert_eq!(iter.next(), Some(&mut 3));
-            assert_eq!(iter.next(), None);
+    fn test_try_extend_exact() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let fits = 4 - len;
+                assert_eq!(tester.try_extend_exact(len..4), Ok(()));
+                assert!(tester.iter().copied().eq(0..4));
+
+                tester.drain_back(fits);
+                let tail = tester.tail();
+                let mut iter = len..5;
+                assert_eq!(
+                    tester.try_extend_exact(&mut iter),
+                    Err(CapacityError { element: () })
+                );
+                assert_eq!(iter.len(), 0);
+                assert!(tester.iter().copied().eq(0..len));
+                assert_eq!(tester.tail(), tail);
+            }
         }
-        {
-            // mutation
-            let mut iter = tester.iter_mut();
-            iter.next().map(|n| *n += 1);
-            iter.next().map(|n| *n += 2);
+    }
+
+    #[test]
+    fn test_fill_to_capacity_with() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4, Wrapping> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let mut calls = 0;
+                tester.fill_to_capacity_with(|| {
+                    calls += 1;
+                    len + calls - 1
+                });
+                assert_eq!(calls, 4 - len);
+                assert!(tester.iter().copied().eq(0..4));
+            }
         }
-        assert_eq!(tester[0], 3);
-        assert_eq!(tester[1], 5);
     }
 
     #[test]
-    fn test_into_iter() {
-        #[derive(Eq, PartialEq, Debug)]
-        struct NoCopy<T>(T);
+    #[cfg(feature = "alloc")]
+    fn test_chunk_by() {
+        let tester: ArrayDeque<usize, 6> = ArrayDeque::new();
+        assert_eq!(tester.chunk_by(|a, b| a == b).next(), None);
 
-        {
-            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.size_hint(), (2, Some(2)));
-            assert_eq!(iter.next(), Some(NoCopy(1)));
-            assert_eq!(iter.next(), Some(NoCopy(2)));
-            assert_eq!(iter.next(), None);
-            assert_eq!(iter.size_hint(), (0, Some(0)));
+        for padding in 0..6 {
+            let mut tester: ArrayDeque<usize, 6> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back([0, 0, 1, 2, 2, 2]);
+            let runs: Vec<Vec<usize>> = tester
+                .chunk_by(|a, b| a == b)
+                .map(|(a, b)| {
+                    assert!(!a.is_empty());
+                    a.iter().chain(b).copied().collect()
+                })
+                .collect();
+            assert_eq!(runs, [vec![0, 0], vec![1], vec![2, 2, 2]]);
+            assert_eq!(tester.chunk_by(|_, _| true).count(), 1);
+            assert_eq!(tester.chunk_by(|_, _| false).count(), 6);
         }
-        {
-            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            tester.pop_front();
-            tester.push_back(NoCopy(3));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.next(), Some(NoCopy(2)));
-            assert_eq!(iter.next(), Some(NoCopy(3)));
-            assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_split_by() {
+        let tester: ArrayDeque<usize, 6> = ArrayDeque::new();
+        let runs: Vec<_> = tester.split_by(|&x| x == 0).collect();
+        assert_eq!(runs, [(&[][..], &[][..])]);
+
+        for padding in 0..6 {
+            let mut tester: ArrayDeque<usize, 6> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            tester.extend_back([0, 1, 2, 0, 0, 3]);
+            let runs: Vec<Vec<usize>> = tester
+                .split_by(|&x| x == 0)
+                .map(|(a, b)| {
+                    assert!(!a.is_empty() || b.is_empty());
+                    a.iter().chain(b).copied().collect()
+                })
+                .collect();
+            assert_eq!(runs, [vec![], vec![1, 2], vec![], vec![3]]);
+            assert_eq!(tester.split_by(|_| true).count(), 7);
+            assert_eq!(tester.split_by(|_| false).count(), 1);
         }
-        {
-            let mut tester: ArrayDeque<NoCopy<u8>, 3> = ArrayDeque::new();
-            tester.push_back(NoCopy(1));
-            tester.push_back(NoCopy(2));
-            tester.pop_front();
-            tester.push_back(NoCopy(3));
-            tester.pop_front();
-            tester.push_back(NoCopy(4));
-            let mut iter = tester.into_iter();
-            assert_eq!(iter.next(), Some(NoCopy(3)));
-            assert_eq!(iter.next(), Some(NoCopy(4)));
-            assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_first_last_chunk() {
+        for padding in 0..5 {
+            let mut tester: ArrayDeque<usize, 5> = ArrayDeque::new();
+            unsafe {
+                tester.set_tail(padding);
+            }
+            assert_eq!(tester.first_chunk::<0>(), Some([]));
+            assert_eq!(tester.last_chunk::<1>(), None);
+            tester.extend_back(0..5);
+            assert_eq!(tester.first_chunk::<3>(), Some([&0, &1, &2]));
+            assert_eq!(tester.last_chunk::<3>(), Some([&2, &3, &4]));
+            assert_eq!(tester.first_chunk::<5>(), Some([&0, &1, &2, &3, &4]));
+            assert_eq!(tester.last_chunk::<5>(), Some([&0, &1, &2, &3, &4]));
+            assert_eq!(tester.first_chunk::<6>(), None);
+        }
+    }
+
+    #[test]
+    fn test_spare_capacity_mut() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let free = if len == 4 {
+                    0
+                } else if padding + len < 4 {
+                    4 - padding - len
+                } else {
+                    padding - (padding + len - 4)
+                };
+                let spare = tester.spare_capacity_mut();
+                assert_eq!(spare.len(), free);
+                if let Some(slot) = spare.first_mut() {
+                    *slot = MaybeUninit::new(len);
+                    unsafe {
+                        tester.set_head_forward();
+                    }
+                    assert!(tester.iter().copied().eq(0..=len));
+                }
+            }
         }
     }
 
     #[test]
-    #[cfg(feature = "std")]
-    fn test_drain() {
-        const CAP: usize = 8;
-        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
-
-        for padding in 0..CAP {
-            for drain_start in 0..CAP {
-                for drain_end in drain_start..CAP {
-                    // deque starts from different tail position
+    fn test_spare_capacity_front_mut() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(1..=len);
+                let free = if len == 4 {
+                    0
+                } else if padding == 0 {
+                    4 - len
+                } else {
+                    cmp::min(padding, 4 - len)
+                };
+                let spare = tester.spare_capacity_front_mut();
+                assert_eq!(spare.len(), free);
+                if let Some(slot) = spare.last_mut() {
+                    *slot = MaybeUninit::new(0);
                     unsafe {
-                        tester.set_len(0);
-                        tester.set_tail(padding);
+                        tester.set_tail_backward();
                     }
-
-                    tester.extend_back(0..CAP);
-
-                    let mut expected = vec![0, 1, 2, 3, 4, 5, 6, 7];
-                    let drains: Vec<_> = tester.drain(drain_start..drain_end).collect();
-                    let expected_drains: Vec<_> = expected.drain(drain_start..drain_end).collect();
-                    assert_eq!(drains, expected_drains);
-                    assert_eq!(tester, expected.into());
+                    assert!(tester.iter().copied().eq(0..=len));
                 }
             }
         }
     }
 
     #[test]
-    fn test_drop() {
-        use std::cell::Cell;
-
-        let flag = &Cell::new(0);
-
-        struct Bump<'a>(&'a Cell<i32>);
-
-        impl<'a> Drop for Bump<'a> {
-            fn drop(&mut self) {
-                let n = self.0.get();
-                self.0.set(n + 1);
+    fn test_commit_back() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let spare = tester.spare_capacity_mut();
+                let n = spare.len();
+                for (i, slot) in spare.iter_mut().enumerate() {
+                    *slot = MaybeUninit::new(len + i);
+                }
+                unsafe {
+                    tester.commit_back(n);
+                }
+                assert!(tester.iter().copied().eq(0..len + n));
             }
         }
+    }
 
-        {
-            let mut tester = ArrayDeque::<Bump, 128>::new();
-            tester.push_back(Bump(flag));
-            tester.push_back(Bump(flag));
-        }
-        assert_eq!(flag.get(), 2);
-
-        // test something with the nullable pointer optimization
-        flag.set(0);
-        {
-            let mut tester = ArrayDeque::<_, 3>::new();
-            tester.push_back(vec![Bump(flag)]);
-            tester.push_back(vec![Bump(flag), Bump(flag)]);
-            tester.push_back(vec![]);
-            tester.push_back(vec![Bump(flag)]);
-            assert_eq!(flag.get(), 1);
-            drop(tester.pop_back());
-            assert_eq!(flag.get(), 1);
-            drop(tester.pop_back());
-            assert_eq!(flag.get(), 3);
+    #[test]
+    #[should_panic]
+    fn test_commit_back_overflow() {
+        let mut tester: ArrayDeque<usize, 4> = [0, 1, 2].into();
+        unsafe {
+            tester.commit_back(2);
         }
-        assert_eq!(flag.get(), 4);
     }
 
     #[test]
-    fn test_as_slice() {
-        const CAP: usize = 10;
-        let mut tester = ArrayDeque::<_, CAP>::new();
-
-        for len in 0..CAP + 1 {
-            for padding in 0..CAP {
-                // deque starts from different tail position
+    fn test_commit_front() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
                 unsafe {
-                    tester.set_len(0);
                     tester.set_tail(padding);
                 }
-
-                let mut expected = vec![];
-                tester.extend_back(0..len);
-                expected.extend(0..len);
-
-                let split_idx = CAP - padding;
-                if split_idx < len {
-                    assert_eq!(tester.as_slices(), expected[..].split_at(split_idx));
-                } else {
-                    assert_eq!(tester.as_slices(), (&expected[..], &[][..]));
+                tester.extend_back(4..4 + len);
+                let spare = tester.spare_capacity_front_mut();
+                let n = spare.len();
+                for (i, slot) in spare.iter_mut().rev().enumerate() {
+                    *slot = MaybeUninit::new(3 - i);
+                }
+                unsafe {
+                    tester.commit_front(n);
                 }
+                assert!(tester.iter().copied().eq(4 - n..4 + len));
             }
         }
     }
 
     #[test]
-    fn test_partial_equal() {
-        const CAP: usize = 10;
-        let mut tester = ArrayDeque::<f64, CAP>::new();
-
-        for len in 0..CAP + 1 {
-            for padding in 0..CAP {
-                // deque starts from different tail position
+    fn test_uninit_slices() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<usize, 4> = ArrayDeque::new();
                 unsafe {
-                    tester.set_len(0);
                     tester.set_tail(padding);
                 }
-
-                let mut expected = ArrayDeque::<f64, CAP>::new();
-                for x in 0..len {
-                    tester.push_back(x as f64);
-                    expected.push_back(x as f64);
+                tester.extend_back(0..len);
+                let spare = tester.spare_capacity_mut().len();
+                let (a, b) = tester.uninit_slices();
+                assert_eq!(a.len(), spare);
+                assert_eq!(a.len() + b.len(), 4 - len);
+                assert!(!a.is_empty() || b.is_empty());
+                for (i, slot) in a.iter_mut().chain(b.iter_mut()).enumerate() {
+                    *slot = MaybeUninit::new(len + i);
                 }
-                assert_eq!(tester, expected);
+                unsafe {
+                    tester.set_len(4);
+                }
+                assert!(tester.iter().copied().eq(0..4));
+            }
+        }
+    }
 
-                // test negative
-                if len > 2 {
-                    tester.pop_front();
-                    expected.pop_back();
-                    assert!(tester != expected);
+    #[test]
+    fn test_read_packet() {
+        for padding in 0..4 {
+            for len in 0..=4 {
+                let mut tester: ArrayDeque<u8, 4, Wrapping> = ArrayDeque::new();
+                unsafe {
+                    tester.set_tail(padding);
                 }
+                tester.extend_back(0..len as u8);
+                assert_eq!(tester.read_packet(&[]), 0);
+                assert_eq!(tester.read_packet(&[0, 1, 2, 3, 4][len..]), 4 - len);
+                assert!(tester.iter().copied().eq(0..4));
+                assert_eq!(tester.read_packet(&[9]), 0);
             }
         }
     }
@@ -2796,6 +8077,43 @@ mod tests {
         assert_eq!(format!("{:?}", tester), "[0, 1, 2, 3]");
     }
 
+    #[test]
+    fn test_fmt_hex() {
+        const CAP: usize = 4;
+        let mut tester = ArrayDeque::<u8, CAP>::new();
+        for padding in 0..CAP {
+            unsafe {
+                tester.set_len(0);
+                tester.set_tail(padding);
+            }
+            assert_eq!(format!("{:x}", tester), "");
+            tester.extend_back([0x00, 0x1f, 0xa0, 0xff]);
+            assert_eq!(format!("{:x}", tester), "001fa0ff");
+            assert_eq!(format!("{:#X}", tester), "0x001FA0FF");
+        }
+    }
+
+    #[test]
+    fn test_reverse_range() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for padding in 0..CAP {
+            for start in 0..CAP + 1 {
+                for end in start..CAP + 1 {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..CAP);
+                    let mut expected: Vec<_> = (0..CAP).collect();
+                    expected[start..end].reverse();
+                    tester.reverse_range(start..end);
+                    assert!(tester.iter().eq(&expected));
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_swap_front_back_remove() {
         fn test(back: bool) {
@@ -2851,11 +8169,135 @@ mod tests {
                 tester.set_len(0);
             }
             tester.extend_back(0..CAP);
-            tester.retain(|x| x % 2 == 0);
+            assert_eq!(tester.retain(|x| x % 2 == 0), CAP / 2);
             assert_eq!(tester.iter().count(), CAP / 2);
         }
     }
 
+    #[test]
+    fn test_merge_sorted() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<_, CAP> = ArrayDeque::new();
+        let mut other: ArrayDeque<_, CAP> = ArrayDeque::new();
+        for padding in 0..CAP {
+            for other_padding in 0..CAP {
+                unsafe {
+                    tester.set_tail(padding);
+                    tester.set_len(0);
+                    other.set_tail(other_padding);
+                    other.set_len(0);
+                }
+                tester.extend_back([0, 3, 3, 6, 9]);
+                other.extend_back([1, 2, 3, 4, 7, 8]);
+                tester.merge_sorted(&mut other);
+                assert_eq!(tester, [0, 1, 2, 3, 3, 3, 6, 9].into());
+                assert_eq!(other, [4, 7, 8].into());
+
+                other.clear();
+                tester.merge_sorted(&mut other);
+                assert_eq!(tester.len(), CAP);
+
+                tester.clear();
+                other.extend_back([5, 6]);
+                tester.merge_sorted(&mut other);
+                assert_eq!(tester, [5, 6].into());
+                assert!(other.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_replace_with_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let elem = Rc::new(());
+        let mut tester: ArrayDeque<_, 4> = ArrayDeque::new();
+        for _ in 0..4 {
+            tester.push_back(elem.clone());
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            tester.replace_with(1, |_| panic!());
+        }));
+        assert!(result.is_err());
+        assert_eq!(tester.len(), 3);
+        assert_eq!(Rc::strong_count(&elem), 4);
+
+        drop(tester);
+        assert_eq!(Rc::strong_count(&elem), 1);
+
+        const CAP: usize = 5;
+        for padding in 0..CAP {
+            for len in 1..=CAP {
+                for index in 0..len {
+                    let mut tester: ArrayDeque<Box<usize>, CAP> = ArrayDeque::new();
+                    unsafe {
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back((0..len).map(Box::new));
+
+                    let result = catch_unwind(AssertUnwindSafe(|| {
+                        tester.replace_with(index, |_| panic!());
+                    }));
+                    assert!(result.is_err());
+                    let expected = (0..len).filter(|&i| i != index);
+                    assert!(tester.iter().map(|x| **x).eq(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_range() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                unsafe {
+                    tester.set_len(0);
+                    tester.set_tail(padding);
+                }
+                tester.extend_back(0..len);
+                let expected: Vec<_> = (0..len).collect();
+                for start in 0..len + 2 {
+                    for end in 0..len + 2 {
+                        if start > end || end > len {
+                            assert_eq!(tester.get_range(start..end), None);
+                            assert_eq!(tester.get_range_mut(start..end), None);
+                            continue;
+                        }
+                        let (a, b) = tester.get_range(start..end).unwrap();
+                        assert_eq!([a, b].concat(), &expected[start..end]);
+                        let (a, b) = tester.get_range_mut(start..end).unwrap();
+                        assert_eq!([&a[..], &b[..]].concat(), &expected[start..end]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        const CAP: usize = 6;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for padding in 0..CAP {
+                for mid in 0..len + 1 {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let expected: Vec<_> = (0..len).collect();
+                    let ((a, b), (c, d)) = tester.split_at_mut(mid);
+                    assert_eq!([&a[..], &b[..]].concat(), &expected[..mid]);
+                    assert_eq!([&c[..], &d[..]].concat(), &expected[mid..]);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_split_off() {
         const CAP: usize = 16;
@@ -2885,6 +8327,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_take_front() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for n in 0..CAP + 2 {
+                for padding in 0..CAP {
+                    let taken = cmp::min(n, len);
+                    let expected_self = (taken..).take(len - taken).collect();
+                    let expected_other = (0..).take(taken).collect();
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let result = tester.take_front(n);
+                    assert!(tester.tail() < CAP);
+                    assert!(result.tail() < CAP);
+                    assert_eq!(tester, expected_self);
+                    assert_eq!(result, expected_other);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_off_back() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for n in 0..CAP + 2 {
+                for padding in 0..CAP {
+                    let taken = cmp::min(n, len);
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    let result = tester.split_off_back(n);
+                    assert!(tester.iter().copied().eq(0..len - taken));
+                    assert!(result.iter().copied().eq(len - taken..len));
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_remove() {
         const CAP: usize = 16;
@@ -2920,7 +8408,7 @@ mod tests {
 
     #[test]
     fn test_clone() {
-        let tester: ArrayDeque<_, 16> = (0..16).into_iter().collect();
+        let tester: ArrayDeque<_, 16> = (0..16).collect();
         let cloned = tester.clone();
         assert_eq!(tester, cloned)
     }
@@ -2960,6 +8448,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_insert_slice() {
+        const CAP: usize = 8;
+        let mut tester = ArrayDeque::<_, CAP>::new();
+        for len in 0..CAP + 1 {
+            for count in 0..CAP - len + 1 {
+                for index in 0..len + 1 {
+                    for padding in 0..CAP {
+                        unsafe {
+                            tester.set_len(0);
+                            tester.set_tail(padding);
+                        }
+                        tester.extend_back(0..len);
+                        let src: Vec<_> = (100..100 + count).collect();
+                        let mut expected: Vec<_> = (0..len).collect();
+                        expected.splice(index..index, src.iter().cloned());
+
+                        assert_eq!(tester.insert_slice(index, &src), Ok(()));
+                        assert!(tester.tail() < CAP);
+                        assert!(tester.iter().eq(&expected));
+
+                        if tester.len() < CAP {
+                            let overflow: Vec<_> = (0..CAP - tester.len() + 1).collect();
+                            assert!(tester.insert_slice(0, &overflow).is_err());
+                            assert!(tester.iter().eq(&expected));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_linearize() {
         let mut tester: ArrayDeque<isize, 10, Saturating> = ArrayDeque::new();
@@ -3060,6 +8580,32 @@ mod tests {
         assert_eq!(tester, [3, 2, 1].into());
     }
 
+    #[test]
+    fn test_extend_front_from_slice() {
+        const CAP: usize = 8;
+        let mut tester: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        let mut expected: ArrayDeque<usize, CAP> = ArrayDeque::new();
+        for len in 0..CAP + 1 {
+            for count in 0..CAP + 2 {
+                for padding in 0..CAP {
+                    unsafe {
+                        tester.set_len(0);
+                        tester.set_tail(padding);
+                        expected.set_len(0);
+                        expected.set_tail(padding);
+                    }
+                    tester.extend_back(0..len);
+                    expected.extend_back(0..len);
+                    let src: Vec<_> = (100..100 + count).collect();
+                    tester.extend_front_from_slice(&src);
+                    expected.extend_front(src.iter().copied());
+                    assert!(tester.tail() < CAP);
+                    assert_eq!(tester, expected);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_extend_back_saturating() {
         let mut tester: ArrayDeque<usize, 3, Saturating> = ArrayDeque::new();
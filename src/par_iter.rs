@@ -0,0 +1,51 @@
+//! Parallel iterators for `ArrayDeque`, available with the `rayon` feature.
+//!
+//! The two halves returned by `as_slices` are chained into a single
+//! parallel iterator, so no elements are copied.
+//!
+//! ```
+//! use arraydeque::ArrayDeque;
+//! use rayon::prelude::*;
+//!
+//! let mut buf: ArrayDeque<u64, 8> = ArrayDeque::new();
+//! buf.extend_back([3, 4, 5]);
+//! buf.extend_front([2, 1, 0]);
+//!
+//! buf.par_iter_mut().for_each(|x| *x *= *x);
+//!
+//! let sum: u64 = buf.par_iter().sum();
+//! assert_eq!(sum, 55);
+//! ```
+
+use rayon::iter::Chain;
+use rayon::prelude::*;
+use rayon::slice;
+
+use crate::behavior::Behavior;
+use crate::ArrayDeque;
+
+impl<'a, T, const CAP: usize, B: Behavior> IntoParallelIterator for &'a ArrayDeque<T, CAP, B>
+where
+    T: Sync,
+{
+    type Item = &'a T;
+    type Iter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (a, b) = self.as_slices();
+        a.par_iter().chain(b.par_iter())
+    }
+}
+
+impl<'a, T, const CAP: usize, B: Behavior> IntoParallelIterator for &'a mut ArrayDeque<T, CAP, B>
+where
+    T: Send,
+{
+    type Item = &'a mut T;
+    type Iter = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (a, b) = self.as_mut_slices();
+        a.par_iter_mut().chain(b.par_iter_mut())
+    }
+}
@@ -1,7 +1,8 @@
-use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
 /// **RangeArgument** is implemented by Rust's built-in range types, produced
-/// by range syntax like `..`, `a..`, `..b` or `c..d`.
+/// by range syntax like `..`, `a..`, `..b`, `c..d`, `..=e` or `f..=g`, and
+/// by pairs of [`Bound`]s, the same set accepted by `Vec::drain`.
 pub trait RangeArgument<T = usize> {
     #[inline]
     /// Start index (inclusive)
@@ -41,3 +42,47 @@ impl<T: Copy> RangeArgument<T> for Range<T> {
         Some(self.end)
     }
 }
+
+impl RangeArgument<usize> for RangeInclusive<usize> {
+    #[inline]
+    fn start(&self) -> Option<usize> {
+        Some(*RangeInclusive::start(self))
+    }
+    #[inline]
+    fn end(&self) -> Option<usize> {
+        let (start, end) = (*RangeInclusive::start(self), *RangeInclusive::end(self));
+        // An exhausted iterator is empty with equal bounds, and like
+        // `RangeBounds` it then excludes its end
+        if self.is_empty() && start == end {
+            return Some(end);
+        }
+        // `usize::MAX` is never a valid index, so saturating keeps the bounds check failing
+        Some(end.saturating_add(1))
+    }
+}
+
+impl RangeArgument<usize> for RangeToInclusive<usize> {
+    #[inline]
+    fn end(&self) -> Option<usize> {
+        Some(self.end.saturating_add(1))
+    }
+}
+
+impl RangeArgument<usize> for (Bound<usize>, Bound<usize>) {
+    #[inline]
+    fn start(&self) -> Option<usize> {
+        match self.0 {
+            Bound::Included(start) => Some(start),
+            Bound::Excluded(start) => Some(start.saturating_add(1)),
+            Bound::Unbounded => None,
+        }
+    }
+    #[inline]
+    fn end(&self) -> Option<usize> {
+        match self.1 {
+            Bound::Included(end) => Some(end.saturating_add(1)),
+            Bound::Excluded(end) => Some(end),
+            Bound::Unbounded => None,
+        }
+    }
+}
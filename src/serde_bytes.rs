@@ -0,0 +1,106 @@
+//! Compact serialization of byte deques, available with the `serde` feature.
+//!
+//! The bytes are serialized as a single byte string with `serialize_bytes`
+//! instead of a sequence of separate `u8`s, and deserialized from either a
+//! byte string or a sequence. Use it on byte deque fields of derived types
+//! with `#[serde(with = "arraydeque::serde_bytes")]`, or call it from manual
+//! implementations:
+//!
+//! ```
+//! use arraydeque::ArrayDeque;
+//! use serde::{Deserialize, Deserializer, Serialize, Serializer};
+//! use serde_test::{assert_tokens, Token};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Payload(ArrayDeque<u8, 64>);
+//!
+//! impl Serialize for Payload {
+//!     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+//!         arraydeque::serde_bytes::serialize(&self.0, serializer)
+//!     }
+//! }
+//!
+//! impl<'de> Deserialize<'de> for Payload {
+//!     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+//!         arraydeque::serde_bytes::deserialize(deserializer).map(Payload)
+//!     }
+//! }
+//!
+//! let payload = Payload((*b"abc").into());
+//! assert_tokens(&payload, &[Token::Bytes(b"abc")]);
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+
+use crate::behavior::Behavior;
+use crate::ArrayDeque;
+
+/// Serialize the bytes of `deque` as a single byte string.
+///
+/// If the bytes wrap around the end of the buffer, they are first copied
+/// into a `[u8; CAP]` on the stack, which may be too large for small stacks
+/// with a large `CAP`. Call `linearize` beforehand to avoid the copy.
+pub fn serialize<S, const CAP: usize, B: Behavior>(
+    deque: &ArrayDeque<u8, CAP, B>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match deque.try_as_slice() {
+        Some(bytes) => serializer.serialize_bytes(bytes),
+        None => {
+            let (a, b) = deque.as_slices();
+            let mut buf = [0; CAP];
+            buf[..a.len()].copy_from_slice(a);
+            buf[a.len()..deque.len()].copy_from_slice(b);
+            serializer.serialize_bytes(&buf[..deque.len()])
+        }
+    }
+}
+
+/// Deserialize a byte deque from a byte string or a sequence of bytes.
+///
+/// Fails if there are more than `CAP` bytes.
+pub fn deserialize<'de, D, const CAP: usize, B: Behavior>(
+    deserializer: D,
+) -> Result<ArrayDeque<u8, CAP, B>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+}
+
+struct BytesVisitor<const CAP: usize, B>(PhantomData<B>);
+
+impl<'de, const CAP: usize, B: Behavior> Visitor<'de> for BytesVisitor<CAP, B> {
+    type Value = ArrayDeque<u8, CAP, B>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "at most {} bytes", CAP)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        ArrayDeque::from_bytes(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut deque = ArrayDeque::new();
+        while let Some(byte) = seq.next_element()? {
+            if deque.put_u8(byte).is_err() {
+                return Err(A::Error::invalid_length(CAP + 1, &self));
+            }
+        }
+        Ok(deque)
+    }
+}